@@ -0,0 +1,61 @@
+// Compares the day 1 digit matcher against checking every word at every position, on a
+// large generated calibration document. Run with `cargo bench --bench day1_matcher`.
+
+use std::time::Instant;
+
+use advent_of_code_2023::day1::{self, DIGIT_WORDS};
+use advent_of_code_2023::generate;
+
+const LINES: usize = 1_000_000;
+
+fn naive_value(line: &str) -> u32 {
+    let digit_at = |s: &str| {
+        if let Some(n) = s.chars().next()?.to_digit(10) {
+            return Some(n);
+        }
+        DIGIT_WORDS
+            .iter()
+            .find(|(word, _)| s.starts_with(word))
+            .map(|(_, n)| *n)
+    };
+    let first = line.char_indices().find_map(|(i, _)| digit_at(&line[i..]));
+    let last = line
+        .char_indices()
+        .rev()
+        .find_map(|(i, _)| digit_at(&line[i..]));
+    match (first, last) {
+        (Some(first), Some(last)) => first * 10 + last,
+        _ => 0,
+    }
+}
+
+fn main() {
+    let document = generate::calibration_document(LINES);
+    let lines: Vec<&str> = document.lines().collect();
+    let megabytes = document.len() as f64 / 1_000_000.0;
+
+    let start = Instant::now();
+    let naive: u64 = lines.iter().map(|line| u64::from(naive_value(line))).sum();
+    let naive_time = start.elapsed();
+
+    let start = Instant::now();
+    let matched = day1::part2(lines.iter().copied());
+    let matcher_time = start.elapsed();
+
+    assert_eq!(naive, matched);
+    println!("{LINES} lines, {megabytes:.1} MB");
+    println!(
+        "naive scan: {:>10.2?} ({:.1} MB/s)",
+        naive_time,
+        megabytes / naive_time.as_secs_f64()
+    );
+    println!(
+        "matcher:    {:>10.2?} ({:.1} MB/s)",
+        matcher_time,
+        megabytes / matcher_time.as_secs_f64()
+    );
+    println!(
+        "speedup:    {:>10.2}x",
+        naive_time.as_secs_f64() / matcher_time.as_secs_f64()
+    );
+}
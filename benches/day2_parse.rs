@@ -0,0 +1,74 @@
+// Measures how fast a large generated day 2 game log parses, against the old parser that
+// compiled its regexes on every line. Run with `cargo bench --bench day2_parse`.
+
+use std::time::{Duration, Instant};
+
+use advent_of_code_2023::day2::GameLog;
+use advent_of_code_2023::generate;
+use advent_of_code_2023::solution::Solution;
+use regex::Regex;
+
+const GAMES: usize = 1_000_000;
+
+// The old parser is so slow it only gets a sample of the log
+const OLD_GAMES: usize = 2_000;
+
+// A round as the amount and name of each color drawn
+type OldRound = Vec<(u32, String)>;
+
+// The game parser from before ids were numbers and the regexes were compiled once, keeping
+// only what it parsed into
+fn old_parse_line(line: &str) -> Option<(String, Vec<OldRound>)> {
+    let re = Regex::new(r"Game (\d+): (.*)").unwrap();
+    let color_re = Regex::new(r"(\d+) (\w+)").unwrap();
+
+    let caps = re.captures(line)?;
+    let id = caps.get(1)?.as_str().to_string();
+    let rounds = caps
+        .get(2)?
+        .as_str()
+        .split(';')
+        .map(|round_data| {
+            color_re
+                .captures_iter(round_data)
+                .filter_map(|cap| Some((cap[1].parse().ok()?, cap[2].to_string())))
+                .collect()
+        })
+        .collect();
+    Some((id, rounds))
+}
+
+fn report(name: &str, games: usize, megabytes: f64, time: Duration) -> f64 {
+    let throughput = megabytes / time.as_secs_f64();
+    println!(
+        "{name}: {:>10.2?} for {games} games ({throughput:.1} MB/s, {:.0} games/s)",
+        time,
+        games as f64 / time.as_secs_f64()
+    );
+    throughput
+}
+
+fn main() {
+    let log = generate::game_log(GAMES);
+    let megabytes = log.len() as f64 / 1_000_000.0;
+    println!("{GAMES} games, {megabytes:.1} MB");
+
+    let start = Instant::now();
+    let games = GameLog::parse(&log).unwrap();
+    let new_time = start.elapsed();
+    assert_eq!(games.games().len(), GAMES);
+
+    let sample: Vec<&str> = log.lines().take(OLD_GAMES).collect();
+    let sample_megabytes = sample.iter().map(|line| line.len() + 1).sum::<usize>() as f64 / 1e6;
+    let start = Instant::now();
+    let parsed = sample
+        .iter()
+        .filter_map(|line| old_parse_line(line))
+        .count();
+    let old_time = start.elapsed();
+    assert_eq!(parsed, OLD_GAMES);
+
+    let old = report("old parse", OLD_GAMES, sample_megabytes, old_time);
+    let new = report("new parse", GAMES, megabytes, new_time);
+    println!("speedup:   {:>10.2}x", new / old);
+}
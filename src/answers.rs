@@ -0,0 +1,95 @@
+use std::{fmt::Display, path::Path};
+
+use serde::Deserialize;
+
+use crate::{
+    error::Result,
+    solution::Answer,
+    utils::{load_with, parse_toml},
+};
+
+// One entry of answers.toml, e.g.
+//
+// [[answer]]
+// day = 2
+// part = 1
+// input = "input.txt"
+// expected = 2551
+#[derive(Deserialize, Debug)]
+pub struct ExpectedAnswer {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub expected: Answer,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct Answers {
+    #[serde(default, rename = "answer")]
+    answers: Vec<ExpectedAnswer>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verification {
+    Pass,
+    Fail { expected: Answer },
+    Unknown,
+}
+
+impl Verification {
+    pub fn status(&self) -> &'static str {
+        match self {
+            Verification::Pass => "pass",
+            Verification::Fail { .. } => "fail",
+            Verification::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verification::Pass => write!(f, "pass"),
+            Verification::Fail { expected } => write!(f, "fail (expected {})", expected),
+            Verification::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl Answers {
+    pub fn parse(str: &str) -> Result<Answers> {
+        parse_toml(str)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Answers> {
+        load_with(path.as_ref(), Answers::parse)
+    }
+
+    pub fn get(&self, day: u8, part: u8, input: &str) -> Option<&Answer> {
+        self.answers
+            .iter()
+            .find(|a| a.day == day && a.part == part && a.input == input)
+            .map(|a| &a.expected)
+    }
+
+    // Every input name that has an expected answer for the given day
+    pub fn inputs_for_day(&self, day: u8) -> Vec<&str> {
+        let mut inputs: Vec<&str> = vec![];
+        for answer in self.answers.iter().filter(|a| a.day == day) {
+            if !inputs.contains(&answer.input.as_str()) {
+                inputs.push(&answer.input);
+            }
+        }
+        inputs
+    }
+
+    pub fn verify(&self, day: u8, part: u8, input: &str, actual: Option<&Answer>) -> Verification {
+        match (self.get(day, part, input), actual) {
+            (None, _) => Verification::Unknown,
+            (Some(expected), Some(actual)) if expected == actual => Verification::Pass,
+            (Some(expected), _) => Verification::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::{
+    error::{AocError, Result},
+    solution::Day,
+};
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub min_ns: u128,
+    pub median_ns: u128,
+    pub max_ns: u128,
+}
+
+impl Stats {
+    // samples must not be empty
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        samples.sort();
+        Stats {
+            min_ns: samples[0].as_nanos(),
+            median_ns: samples[samples.len() / 2].as_nanos(),
+            max_ns: samples[samples.len() - 1].as_nanos(),
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        nanos(self.min_ns)
+    }
+
+    pub fn median(&self) -> Duration {
+        nanos(self.median_ns)
+    }
+
+    pub fn max(&self) -> Duration {
+        nanos(self.max_ns)
+    }
+}
+
+fn nanos(ns: u128) -> Duration {
+    Duration::from_nanos(ns.try_into().unwrap_or(u64::MAX))
+}
+
+#[derive(Serialize, Debug)]
+pub struct DayBench {
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    // None for parts that are not solved yet
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+}
+
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+// Parses and solves the input `iterations` times, timing each step separately
+pub fn bench_day(day: &Day, input: &str, iterations: usize) -> Result<DayBench> {
+    let iterations = iterations.max(1);
+    let mut parse = vec![];
+    let mut parts = [vec![], vec![]];
+
+    for _ in 0..iterations {
+        let (solution, elapsed) = time(|| day.parse(input));
+        let solution = solution?;
+        parse.push(elapsed);
+
+        for (i, samples) in parts.iter_mut().enumerate() {
+            let (answer, elapsed) = time(|| solution.part(i as u8 + 1));
+            match answer {
+                Ok(_) => samples.push(elapsed),
+                Err(AocError::Unsolved) => (),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    let [mut part1, mut part2] = parts;
+    let stats =
+        |samples: &mut Vec<Duration>| (!samples.is_empty()).then(|| Stats::from_samples(samples));
+
+    Ok(DayBench {
+        day: day.day,
+        iterations,
+        parse: Stats::from_samples(&mut parse),
+        part1: stats(&mut part1),
+        part2: stats(&mut part2),
+    })
+}
@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use advent_of_code_2023::day2::{Bag, Color};
+use advent_of_code_2023::error::Result;
+use clap::Args;
+use log::error;
+
+use crate::games::GameLogArgs;
+
+#[derive(Args)]
+pub struct BagArgs {
+    #[command(flatten)]
+    games: GameLogArgs,
+
+    #[command(flatten)]
+    bag: BagFileArgs,
+}
+
+// The bag to check games against, shared with `infer-bag`
+#[derive(Args)]
+pub struct BagFileArgs {
+    /// TOML file with the cubes in the bag, see src/day2/bag.rs. Defaults to the puzzle's bag
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Cubes of a color in the bag as COLOR=COUNT, e.g. `--cubes red=12`. Can be repeated and
+    /// overrides the config file
+    #[arg(long, value_parser = parse_cubes)]
+    cubes: Vec<(String, u32)>,
+}
+
+fn parse_cubes(arg: &str) -> std::result::Result<(String, u32), String> {
+    let (color, count) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected COLOR=COUNT, got {arg:?}"))?;
+    let count = count
+        .parse()
+        .map_err(|err| format!("invalid count {count:?}: {err}"))?;
+    Ok((color.to_string(), count))
+}
+
+impl BagFileArgs {
+    pub fn load(&self) -> Result<Bag> {
+        let mut bag = match &self.config {
+            Some(path) => {
+                Bag::load(path).inspect_err(|_| error!("Could not load bag {}", path.display()))?
+            }
+            None => Bag::puzzle(),
+        };
+
+        for (color, count) in self.cubes.iter() {
+            bag.set(Color::named(color), *count);
+        }
+        Ok(bag)
+    }
+}
+
+// Prints why each impossible game is impossible, then the sum of the possible ids
+pub fn run(args: BagArgs) -> ExitCode {
+    let bag = match args.bag.load() {
+        Ok(bag) => bag,
+        Err(err) => {
+            error!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(log) = args.games.load() else {
+        return ExitCode::FAILURE;
+    };
+
+    println!("bag: {bag}");
+    for game in log.games() {
+        for violation in game.is_possible_with(&bag) {
+            println!("Game {}: {violation}", game.id);
+        }
+    }
+    println!("{}", log.possible_id_sum(&bag));
+    ExitCode::SUCCESS
+}
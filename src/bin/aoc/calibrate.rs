@@ -0,0 +1,120 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use advent_of_code_2023::day1::{self, DigitMatcher, Match, Vocabulary};
+use advent_of_code_2023::error::Result;
+use advent_of_code_2023::utils::{self, Input};
+use clap::Args;
+use log::error;
+
+#[derive(Args)]
+pub struct CalibrateArgs {
+    /// Calibration document, `-` for stdin. Defaults to the day 1 input
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// TOML file with the digit words to use instead of English, see src/day1/vocabulary.rs
+    #[arg(long, conflicts_with = "numerals_only")]
+    vocab: Option<PathBuf>,
+
+    /// Match the digit words in any case
+    #[arg(long)]
+    ignore_case: bool,
+
+    /// Only count numerals, like part 1
+    #[arg(long)]
+    numerals_only: bool,
+
+    /// Print how the value of every line was found
+    #[arg(long)]
+    explain: bool,
+}
+
+fn vocabulary(args: &CalibrateArgs) -> Result<Vocabulary> {
+    let vocabulary = match (&args.vocab, args.numerals_only) {
+        (Some(path), _) => Vocabulary::load(path)
+            .inspect_err(|_| error!("Could not load vocabulary {}", path.display()))?,
+        (None, true) => Vocabulary::new::<&str>([]),
+        (None, false) => Vocabulary::english(),
+    };
+
+    // the flag can only turn case folding on, a file may already have it set
+    let ignore_case = vocabulary.ignore_case || args.ignore_case;
+    Ok(vocabulary.ignore_case(ignore_case))
+}
+
+fn calibrate(args: &CalibrateArgs) -> Result<u64> {
+    let matcher = vocabulary(args)?.matcher();
+    let input = Input::resolve(1, args.input.as_deref());
+
+    if args.explain {
+        Ok(explain(&input.read()?, &matcher))
+    } else {
+        // streamed so inputs larger than memory can be piped through
+        day1::calibration_sum_reader(input.open()?, &matcher)
+    }
+}
+
+fn describe(line: &str, found: Option<Match>) -> String {
+    match found {
+        Some(found) => format!("{} @{}", found.text(line), found.start),
+        None => "-".to_string(),
+    }
+}
+
+// Prints a table with a row for every line and returns the total
+fn explain(contents: &str, matcher: &DigitMatcher) -> u64 {
+    let width = utils::lines(contents)
+        .map(|(_, line)| line.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("text".len());
+
+    println!(
+        "{:>6} | {:<width$} | {:<12} | {:<12} | value",
+        "line", "text", "first", "last"
+    );
+
+    let mut total = 0;
+    let mut without_digits = 0;
+    for (line_num, line) in utils::lines(contents) {
+        let explanation = day1::explain(line, matcher);
+        let value = match explanation.value() {
+            Some(value) => {
+                total += u64::from(value);
+                value.to_string()
+            }
+            None => {
+                without_digits += 1;
+                "NO DIGITS".to_string()
+            }
+        };
+
+        println!(
+            "{:>6} | {:<width$} | {:<12} | {:<12} | {}",
+            line_num,
+            line,
+            describe(line, explanation.first),
+            describe(line, explanation.last),
+            value
+        );
+    }
+
+    if without_digits > 0 {
+        println!("lines without digits (counted as 0): {without_digits}");
+    }
+    total
+}
+
+pub fn run(args: CalibrateArgs) -> ExitCode {
+    match calibrate(&args) {
+        Ok(total) => {
+            println!("{total}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            error!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use advent_of_code_2023::day2::GameLog;
+use advent_of_code_2023::utils::Input;
+use clap::Args;
+use log::error;
+
+// Where the day 2 commands read their game log from
+#[derive(Args)]
+pub struct GameLogArgs {
+    /// Game log, `-` for stdin. Defaults to the day 2 input
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// Stop at the first malformed game instead of skipping it
+    #[arg(long)]
+    strict: bool,
+}
+
+impl GameLogArgs {
+    // None if the log could not be read, or has a malformed game in strict mode
+    pub fn load(&self) -> Option<GameLog> {
+        let input = Input::resolve(2, self.input.as_deref());
+        let contents = match input.read() {
+            Ok(contents) => contents,
+            Err(err) => {
+                error!("{err}");
+                return None;
+            }
+        };
+
+        match GameLog::parse_with(&contents, self.strict) {
+            Ok(log) => Some(log),
+            Err(err) => {
+                error!("malformed game in {input}\n{}", err.render());
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::process::ExitCode;
+
+use advent_of_code_2023::day2::{Inference, MAX_CANDIDATES, MAX_EXTRA};
+use clap::Args;
+use log::{error, warn};
+
+use crate::bag::BagFileArgs;
+use crate::games::GameLogArgs;
+
+#[derive(Args)]
+pub struct InferArgs {
+    #[command(flatten)]
+    games: GameLogArgs,
+
+    /// Bag to audit against the most likely ones
+    #[command(flatten)]
+    bag: BagFileArgs,
+
+    /// Search bags with up to this many more cubes of each color than the smallest feasible
+    /// bag, at most 10000. Every one of them is ranked if there are at most a million. The
+    /// likelihood usually keeps rising with bigger bags, so this often decides the answer
+    #[arg(
+        short,
+        long,
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u32).range(..=i64::from(MAX_EXTRA))
+    )]
+    extra: u32,
+
+    /// How many of the most likely bags to print
+    #[arg(short = 'n', long, default_value_t = 10)]
+    top: usize,
+}
+
+// Prints the feasible region, the most likely bags and where the audited bag ranks among them
+pub fn run(args: InferArgs) -> ExitCode {
+    let audited = match args.bag.load() {
+        Ok(bag) => bag,
+        Err(err) => {
+            error!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(log) = args.games.load() else {
+        return ExitCode::FAILURE;
+    };
+
+    let inference = Inference::new(&log);
+    println!("feasible: at least {}", inference.min_bag());
+
+    let optimum = inference.most_likely(args.extra);
+    println!(
+        "most likely: {}, log likelihood {:.4}",
+        optimum.best.bag, optimum.best.log_likelihood
+    );
+    if !optimum.on_edge.is_empty() {
+        let colors: Vec<String> = optimum.on_edge.iter().map(|c| c.to_string()).collect();
+        let hint = if args.extra < MAX_EXTRA {
+            "try a bigger --extra"
+        } else {
+            "it may keep rising without end"
+        };
+        warn!(
+            "More {} than --extra {} allows is more likely still, {hint}",
+            colors.join(", "),
+            args.extra
+        );
+    }
+
+    let Some(candidates) = inference.rank(args.extra) else {
+        warn!(
+            "Not ranking {} bags, more than {MAX_CANDIDATES}, lower --extra to rank them",
+            inference.candidates(args.extra)
+        );
+        println!();
+        match inference.log_likelihood(&audited) {
+            Some(log_likelihood) => println!("{audited}: log likelihood {log_likelihood:.4}"),
+            None => println!("{audited}: not feasible"),
+        }
+        return ExitCode::SUCCESS;
+    };
+
+    println!();
+    println!("{:>6} | {:>14} | bag", "rank", "log likelihood");
+    for (i, candidate) in candidates.iter().take(args.top).enumerate() {
+        println!(
+            "{:>6} | {:>14.4} | {}",
+            i + 1,
+            candidate.log_likelihood,
+            candidate.bag
+        );
+    }
+
+    println!();
+    match inference.log_likelihood(&audited) {
+        Some(log_likelihood) => {
+            let better = candidates
+                .iter()
+                .filter(|candidate| candidate.log_likelihood > log_likelihood)
+                .count();
+            println!(
+                "{audited}: log likelihood {log_likelihood:.4}, {better} of the {} ranked bags are more likely",
+                candidates.len()
+            );
+        }
+        None => println!("{audited}: not feasible"),
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,426 @@
+mod bag;
+mod calibrate;
+mod games;
+mod infer;
+mod report;
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use advent_of_code_2023::answers::{Answers, Verification};
+use advent_of_code_2023::bench::{bench_day, time};
+use advent_of_code_2023::error::AocError;
+use advent_of_code_2023::solution::{self, Answer, Day, Solution, DAYS};
+use advent_of_code_2023::utils::{sha256_hex, Input};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use log::{error, info, warn, LevelFilter};
+use serde::Serialize;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Show more diagnostics, repeat for more detail (-v info, -vv debug, -vvv trace)
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Show fewer diagnostics, repeat to hide errors too
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "verbose")]
+    quiet: u8,
+}
+
+// Takes the same filters as RUST_LOG, e.g. AOC_LOG=debug or AOC_LOG=advent_of_code_2023::day3=trace
+const LOG_ENV_VAR: &str = "AOC_LOG";
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution for a day, or for every day with --all
+    Run(RunArgs),
+    /// Check every solution against the expected answers
+    Verify(VerifyArgs),
+    /// Time parsing and solving every day
+    Bench(BenchArgs),
+    /// Sum day 1 calibration values with a custom digit vocabulary
+    Calibrate(calibrate::CalibrateArgs),
+    /// Check which day 2 games are possible with a given bag of cubes
+    Bag(bag::BagArgs),
+    /// Minimum bags and per color draw statistics for a day 2 game log
+    CubeReport(report::ReportArgs),
+    /// Find the bags of cubes that could have produced a day 2 game log
+    InferBag(infer::InferArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to run
+    #[arg(short, long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+
+    /// Part to run, both parts are run if omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Puzzle input, `-` for stdin. Defaults to src/dayN/input.txt or $AOC_INPUT_DIR/dayN/input.txt
+    #[arg(short, long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+
+    /// Run every day
+    #[arg(long)]
+    all: bool,
+
+    /// How to print the results
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// File with the expected answers, used for the verification status in json output
+    #[arg(short, long, default_value = DEFAULT_ANSWERS)]
+    answers: PathBuf,
+
+    /// Fail on input a day would otherwise skip with a warning, like malformed day 2 games
+    #[arg(long)]
+    strict: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, PartialEq)]
+enum Format {
+    /// Tables and plain lines for reading in a terminal
+    Text,
+    /// JSON for other programs, `run` prints one object per line for each day and part
+    Json,
+}
+
+const DEFAULT_ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml");
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// File with the expected answers
+    #[arg(short, long, default_value = DEFAULT_ANSWERS)]
+    answers: PathBuf,
+
+    /// Fail on input a day would otherwise skip with a warning, like malformed day 2 games
+    #[arg(long)]
+    strict: bool,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Only bench this day
+    #[arg(short, long)]
+    day: Option<u8>,
+
+    /// How many times to parse and solve each day
+    #[arg(short = 'n', long, default_value_t = 10)]
+    iterations: usize,
+
+    /// Where to write the JSON summary
+    #[arg(short, long, default_value = "bench.json")]
+    output: PathBuf,
+}
+
+fn parse_input(day: &Day, input: &Input, strict: bool) -> Option<Box<dyn Solution>> {
+    info!("Day {}: reading {input}", day.day);
+    let contents = match input.read() {
+        Ok(contents) => contents,
+        Err(err) => {
+            error!("Day {}: {err}", day.day);
+            return None;
+        }
+    };
+    match day.parse_with(&contents, strict) {
+        Ok(solution) => Some(solution),
+        Err(err) => {
+            error!("Day {} ({input}): {}", day.day, err.render(&contents));
+            None
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PartRecord {
+    day: u8,
+    part: u8,
+    answer: Option<Answer>,
+    error: Option<String>,
+    parse_ns: u128,
+    solve_ns: u128,
+    input: String,
+    input_sha256: String,
+    verification: &'static str,
+    expected: Option<Answer>,
+}
+
+// Returns false if anything failed
+fn run_day(
+    day: &Day,
+    parts: &[u8],
+    input: Option<PathBuf>,
+    format: Format,
+    answers: &Answers,
+    strict: bool,
+) -> bool {
+    let input = Input::resolve(day.day, input.as_deref());
+    info!("Day {}: reading {input}", day.day);
+    let contents = match input.read() {
+        Ok(contents) => contents,
+        Err(err) => {
+            error!("Day {}: {err}", day.day);
+            return false;
+        }
+    };
+    let input_name = input.name(day.day);
+
+    let (solution, parse_time) = time(|| day.parse_with(&contents, strict));
+    if let Err(err) = &solution {
+        error!("Day {} ({input}): {}", day.day, err.render(&contents));
+    }
+
+    let mut ok = solution.is_ok();
+    for &part in parts {
+        let (answer, solve_time) = match &solution {
+            Ok(solution) => time(|| solution.part(part)),
+            Err(err) => (
+                Err(AocError::no_answer(err.to_string())),
+                Default::default(),
+            ),
+        };
+
+        match &answer {
+            Ok(_) | Err(AocError::Unsolved) => (),
+            Err(err) => {
+                if solution.is_ok() {
+                    error!("Day {} part {part}: {err}", day.day);
+                }
+                ok = false;
+            }
+        }
+
+        match format {
+            Format::Text => match &answer {
+                Ok(answer) => println!("Day {} part {part}: {answer}", day.day),
+                Err(AocError::Unsolved) => {
+                    println!("Day {} part {part}: not solved yet", day.day)
+                }
+                Err(_) => (),
+            },
+            Format::Json => {
+                let answer = answer.map_err(|err| err.to_string());
+                let verification = match &input_name {
+                    Some(name) => answers.verify(day.day, part, name, answer.as_ref().ok()),
+                    None => Verification::Unknown,
+                };
+                let expected = match &verification {
+                    Verification::Fail { expected } => Some(expected.clone()),
+                    Verification::Pass => answer.as_ref().ok().cloned(),
+                    Verification::Unknown => None,
+                };
+                let record = PartRecord {
+                    day: day.day,
+                    part,
+                    error: answer.as_ref().err().cloned(),
+                    answer: answer.ok(),
+                    parse_ns: parse_time.as_nanos(),
+                    solve_ns: solve_time.as_nanos(),
+                    input: input.to_string(),
+                    input_sha256: sha256_hex(contents.as_bytes()),
+                    verification: verification.status(),
+                    expected,
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string(&record).expect("run records serialize")
+                );
+            }
+        }
+    }
+    ok
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let answers = match args.format {
+        Format::Text => Answers::default(),
+        Format::Json => Answers::load(&args.answers).unwrap_or_else(|err| {
+            warn!("Could not load {}: {err}", args.answers.display());
+            Answers::default()
+        }),
+    };
+
+    let ok = match args.day {
+        Some(day) => match solution::get_day(day) {
+            Some(day) => run_day(day, &parts, args.input, args.format, &answers, args.strict),
+            None => {
+                error!("Day {day} is not solved yet");
+                false
+            }
+        },
+        None => {
+            let mut ok = true;
+            for day in DAYS {
+                ok &= run_day(day, &parts, None, args.format, &answers, args.strict);
+            }
+            ok
+        }
+    };
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn verify(args: VerifyArgs) -> ExitCode {
+    let answers = match Answers::load(&args.answers) {
+        Ok(answers) => answers,
+        Err(err) => {
+            error!("Could not load {}: {}", args.answers.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failures = 0;
+    for day in DAYS {
+        let mut inputs = answers.inputs_for_day(day.day);
+        if !inputs.contains(&"input.txt") {
+            inputs.push("input.txt");
+        }
+
+        for input in inputs {
+            let Some(solution) = parse_input(day, &Input::named(day.day, input), args.strict)
+            else {
+                failures += 1;
+                continue;
+            };
+
+            for part in 1..=2 {
+                let answer = solution.part(part);
+                let verification = answers.verify(day.day, part, input, answer.as_ref().ok());
+                if let Verification::Fail { .. } = verification {
+                    failures += 1;
+                }
+
+                match answer {
+                    Ok(answer) => println!(
+                        "Day {} part {part} ({input}): {verification}, got {answer}",
+                        day.day
+                    ),
+                    Err(err) => println!(
+                        "Day {} part {part} ({input}): {verification}, {err}",
+                        day.day
+                    ),
+                }
+            }
+        }
+    }
+
+    if failures > 0 {
+        println!("{failures} failed");
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench(args: BenchArgs) -> ExitCode {
+    let days: Vec<&Day> = match args.day {
+        Some(day) => solution::get_day(day).into_iter().collect(),
+        None => DAYS.iter().collect(),
+    };
+    if days.is_empty() {
+        error!("Nothing to bench");
+        return ExitCode::FAILURE;
+    }
+
+    let mut results = vec![];
+    for day in days {
+        let input = Input::resolve(day.day, None);
+        let result = input
+            .read()
+            .and_then(|contents| bench_day(day, &contents, args.iterations));
+        match result {
+            Ok(result) => results.push(result),
+            Err(err) => {
+                error!("Day {} ({input}): {err}", day.day);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    println!(
+        "{:<4} {:<6} {:>12} {:>12} {:>12}",
+        "day", "step", "min", "median", "max"
+    );
+    for result in results.iter() {
+        let steps = [
+            ("parse", Some(&result.parse)),
+            ("part1", result.part1.as_ref()),
+            ("part2", result.part2.as_ref()),
+        ];
+        for (step, stats) in steps {
+            if let Some(stats) = stats {
+                println!(
+                    "{:<4} {:<6} {:>12} {:>12} {:>12}",
+                    result.day,
+                    step,
+                    format!("{:.2?}", stats.min()),
+                    format!("{:.2?}", stats.median()),
+                    format!("{:.2?}", stats.max())
+                );
+            }
+        }
+    }
+
+    let summary = serde_json::to_string_pretty(&results).expect("bench results serialize");
+    if let Err(err) = fs::write(&args.output, summary) {
+        error!("Could not write {}: {err}", args.output.display());
+        return ExitCode::FAILURE;
+    }
+    println!("Summary written to {}", args.output.display());
+
+    ExitCode::SUCCESS
+}
+
+// Only warnings and errors are shown by default, the flags take priority over AOC_LOG
+fn init_logging(verbose: u8, quiet: u8) {
+    let mut builder = env_logger::Builder::new();
+    builder
+        .filter_level(LevelFilter::Warn)
+        .format_timestamp(None)
+        .parse_env(env_logger::Env::new().filter(LOG_ENV_VAR));
+
+    let level = match (verbose, quiet) {
+        (0, 0) => None,
+        (0, 1) => Some(LevelFilter::Error),
+        (0, _) => Some(LevelFilter::Off),
+        (1, _) => Some(LevelFilter::Info),
+        (2, _) => Some(LevelFilter::Debug),
+        _ => Some(LevelFilter::Trace),
+    };
+    if let Some(level) = level {
+        builder.filter_level(level);
+    }
+
+    builder.init();
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_logging(cli.verbose, cli.quiet);
+
+    match cli.command {
+        Command::Run(args) => run(args),
+        Command::Verify(args) => verify(args),
+        Command::Bench(args) => bench(args),
+        Command::Calibrate(args) => calibrate::run(args),
+        Command::Bag(args) => bag::run(args),
+        Command::CubeReport(args) => report::run(args),
+        Command::InferBag(args) => infer::run(args),
+    }
+}
@@ -0,0 +1,68 @@
+use std::process::ExitCode;
+
+use advent_of_code_2023::day2::{Bag, Report};
+use clap::Args;
+
+use crate::games::GameLogArgs;
+use crate::Format;
+
+#[derive(Args)]
+pub struct ReportArgs {
+    #[command(flatten)]
+    games: GameLogArgs,
+
+    /// How to print the report
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+fn print_table(report: &Report) {
+    let colors: Vec<_> = report.min_bag.iter().map(|(color, _)| color).collect();
+    let cubes = |bag: &Bag| -> String {
+        colors
+            .iter()
+            .map(|color| format!(" {:>8}", bag.limit(color)))
+            .collect()
+    };
+
+    let header: String = colors.iter().map(|color| format!(" {color:>8}")).collect();
+    println!("{:>8} |{header} | {:>10}", "game", "power");
+    for game in report.games.iter() {
+        println!(
+            "{:>8} |{} | {:>10}",
+            game.id,
+            cubes(&game.min_bag),
+            game.power
+                .map_or_else(|| "overflow".to_string(), |power| power.to_string())
+        );
+    }
+    println!("{:>8} |{} |", "all", cubes(&report.min_bag));
+
+    println!();
+    println!(
+        "{:>8} | {:>8} | {:>8} | {:>8} | {:>8} | max in game",
+        "color", "draws", "min", "max", "mean"
+    );
+    for stats in report.colors.iter() {
+        println!(
+            "{:>8} | {:>8} | {:>8} | {:>8} | {:>8.2} | {}",
+            stats.color, stats.draws, stats.min, stats.max, stats.mean, stats.max_game
+        );
+    }
+}
+
+pub fn run(args: ReportArgs) -> ExitCode {
+    let Some(log) = args.games.load() else {
+        return ExitCode::FAILURE;
+    };
+    let report = Report::new(&log);
+
+    match args.format {
+        Format::Text => print_table(&report),
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string(&report).expect("reports serialize")
+        ),
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,220 @@
+// Finds the first and last digit in a line, written either as a numeral or as one of a
+// vocabulary of words. The words are compiled once into two Aho-Corasick automatons, one
+// over the words and one over the words reversed, so a line is read at most once from the
+// front and once from the back and nothing is allocated per line.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    // byte offset into the line
+    pub start: usize,
+    // length in bytes
+    pub len: usize,
+    pub digit: u32,
+}
+
+impl Match {
+    // The matched text, `line` must be the line that was searched
+    pub fn text<'a>(&self, line: &'a str) -> &'a str {
+        &line[self.start..self.start + self.len]
+    }
+}
+
+struct Automaton {
+    // transitions[state + class] is the next state, states are stored premultiplied by the
+    // number of classes so they index straight into the table
+    transitions: Vec<u32>,
+    // the longest pattern ending at each state packed as `length << 4 | digit`, 0 if no
+    // pattern ends there. Indexed by state
+    longest: Vec<u32>,
+}
+
+fn unpack(output: u32) -> (usize, u32) {
+    ((output >> 4) as usize, output & 0xf)
+}
+
+pub struct DigitMatcher {
+    // maps each byte to a smaller alphabet of the bytes that appear in a pattern, 0 is "other"
+    classes: [u16; 256],
+    forward: Automaton,
+    backward: Automaton,
+    max_len: usize,
+}
+
+impl DigitMatcher {
+    // Numerals are always matched, `words` are matched on top of them. Digits must be 0 to 9
+    pub fn new(words: &[(&str, u32)]) -> DigitMatcher {
+        DigitMatcher::build(words, false)
+    }
+
+    // ASCII letters match in either case. Other letters match if the word is written all in
+    // lower or all in upper case, e.g. "fünf" matches "FÜNF" and "Fünf" but not "fÜnf"
+    pub fn ignoring_case(words: &[(&str, u32)]) -> DigitMatcher {
+        DigitMatcher::build(words, true)
+    }
+
+    fn build(words: &[(&str, u32)], ignore_case: bool) -> DigitMatcher {
+        let mut patterns: Vec<(Vec<u8>, u32)> =
+            (0..=9).map(|n| (vec![b'0' + n as u8], n)).collect();
+        for (word, n) in words.iter().filter(|(word, n)| !word.is_empty() && *n <= 9) {
+            patterns.push((word.as_bytes().to_vec(), *n));
+            if ignore_case {
+                patterns.push((word.to_lowercase().into_bytes(), *n));
+                patterns.push((word.to_uppercase().into_bytes(), *n));
+            }
+        }
+
+        let mut classes = [0u16; 256];
+        let mut class_count = 1;
+        for (pattern, _) in patterns.iter() {
+            for &b in pattern {
+                if classes[b as usize] == 0 {
+                    classes[b as usize] = class_count as u16;
+                    if ignore_case {
+                        classes[b.to_ascii_lowercase() as usize] = class_count as u16;
+                        classes[b.to_ascii_uppercase() as usize] = class_count as u16;
+                    }
+                    class_count += 1;
+                }
+            }
+        }
+
+        let reversed: Vec<(Vec<u8>, u32)> = patterns
+            .iter()
+            .map(|(pattern, n)| (pattern.iter().rev().copied().collect(), *n))
+            .collect();
+
+        DigitMatcher {
+            forward: Automaton::build(&patterns, &classes, class_count),
+            backward: Automaton::build(&reversed, &classes, class_count),
+            max_len: patterns.iter().map(|(p, _)| p.len()).max().unwrap_or(1),
+            classes,
+        }
+    }
+
+    // The match that starts earliest, the longest one if several start at the same place
+    pub fn first(&self, line: &str) -> Option<Match> {
+        let mut best: Option<Match> = None;
+        let mut state = 0;
+
+        for (i, &b) in line.as_bytes().iter().enumerate() {
+            if let Some(best) = best {
+                // nothing that ends from here on can start at or before the best match
+                if i >= best.start + self.max_len {
+                    break;
+                }
+            }
+
+            state = self.forward.next(state, self.class(b));
+            let output = self.forward.longest[state];
+            if output != 0 {
+                let (len, digit) = unpack(output);
+                let start = i + 1 - len;
+                let better = match best {
+                    None => true,
+                    Some(best) => start < best.start || (start == best.start && len > best.len),
+                };
+                if better {
+                    best = Some(Match { start, len, digit });
+                }
+            }
+        }
+
+        best
+    }
+
+    // The match that starts latest, the longest one if several start at the same place
+    pub fn last(&self, line: &str) -> Option<Match> {
+        let bytes = line.as_bytes();
+        let mut state = 0;
+
+        for (i, &b) in bytes.iter().enumerate().rev() {
+            state = self.backward.next(state, self.class(b));
+            let output = self.backward.longest[state];
+            if output != 0 {
+                let (len, digit) = unpack(output);
+                return Some(Match {
+                    start: i,
+                    len,
+                    digit,
+                });
+            }
+        }
+
+        None
+    }
+
+    fn class(&self, b: u8) -> usize {
+        self.classes[b as usize] as usize
+    }
+}
+
+impl Automaton {
+    fn build(patterns: &[(Vec<u8>, u32)], classes: &[u16; 256], class_count: usize) -> Automaton {
+        const NONE: u32 = u32::MAX;
+
+        // trie of the patterns, state 0 is the root
+        let mut transitions = vec![NONE; class_count];
+        let mut longest: Vec<u32> = vec![0];
+        for (pattern, digit) in patterns {
+            let mut state = 0;
+            for &b in pattern {
+                let slot = state * class_count + classes[b as usize] as usize;
+                if transitions[slot] == NONE {
+                    transitions[slot] = longest.len() as u32;
+                    transitions.extend(std::iter::repeat_n(NONE, class_count));
+                    longest.push(0);
+                }
+                state = transitions[slot] as usize;
+            }
+            // the first pattern wins if the vocabulary has duplicates
+            if longest[state] == 0 {
+                longest[state] = (pattern.len() as u32) << 4 | digit;
+            }
+        }
+
+        // breadth first, filling in missing transitions from the failure links so every
+        // state has a transition for every class
+        let mut fail = vec![0usize; longest.len()];
+        let mut queue = std::collections::VecDeque::new();
+        for slot in transitions[..class_count].iter_mut() {
+            match *slot {
+                NONE => *slot = 0,
+                next => queue.push_back(next as usize),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            if longest[state] == 0 {
+                longest[state] = longest[fail[state]];
+            }
+            for class in 0..class_count {
+                let slot = state * class_count + class;
+                let fallback = transitions[fail[state] * class_count + class];
+                match transitions[slot] {
+                    NONE => transitions[slot] = fallback,
+                    next => {
+                        fail[next as usize] = fallback as usize;
+                        queue.push_back(next as usize);
+                    }
+                }
+            }
+        }
+
+        // premultiply the states, spreading `longest` out to match
+        for next in transitions.iter_mut() {
+            *next *= class_count as u32;
+        }
+        let mut spread = vec![0; transitions.len()];
+        for (state, output) in longest.into_iter().enumerate() {
+            spread[state * class_count] = output;
+        }
+
+        Automaton {
+            transitions,
+            longest: spread,
+        }
+    }
+
+    fn next(&self, state: usize, class: usize) -> usize {
+        self.transitions[state + class] as usize
+    }
+}
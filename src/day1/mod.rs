@@ -1,99 +1,369 @@
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::{self, BufRead};
-use std::path::Path;
+mod matcher;
+mod vocabulary;
 
-pub fn run() {
-    let path = Path::new("input.txt");
+use std::{io::BufRead, sync::OnceLock};
 
-    let mut file = match File::open(&path) {
-        Err(why) => panic!("couldn't open {}: {}", path.display(), why),
-        Ok(file) => file,
-    };
+use log::{debug, warn};
+
+pub use matcher::{DigitMatcher, Match};
+pub use vocabulary::Vocabulary;
 
-    let mut contents = String::new();
+use crate::{
+    error::{AocError, Result},
+    solution::{Answer, Solution},
+    utils,
+};
 
-    match file.read_to_string(&mut contents) {
-        Err(why) => panic!("couldn't read {}: {}", path.display(), why),
-        Ok(_) => (),
+pub struct CalibrationDocument {
+    lines: Vec<String>,
+}
+
+impl Solution for CalibrationDocument {
+    fn parse(input: &str) -> Result<CalibrationDocument> {
+        Ok(CalibrationDocument {
+            lines: utils::lines(input)
+                .map(|(_, line)| line.to_string())
+                .collect(),
+        })
     }
 
-    println!("Part 1: {}", &contents);
+    fn part1(&self) -> Result<Answer> {
+        part1(self.lines.iter().map(String::as_str)).try_into()
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        part2(self.lines.iter().map(String::as_str)).try_into()
+    }
+}
+
+// Sum of the calibration values using only numerals
+pub fn part1<'a>(lines: impl IntoIterator<Item = &'a str>) -> u64 {
+    static NUMERALS: OnceLock<DigitMatcher> = OnceLock::new();
+    calibration_sum(lines, NUMERALS.get_or_init(|| DigitMatcher::new(&[])))
+}
 
-    let letters = [
-        ["one", "1"],
-        ["two", "2"],
-        ["three", "3"],
-        ["four", "4"],
-        ["five", "5"],
-        ["six", "6"],
-        ["seven", "7"],
-        ["eight", "8"],
-        ["nine", "9"],
-    ];
+// Sum of the calibration values using numerals and spelled out digits
+pub fn part2<'a>(lines: impl IntoIterator<Item = &'a str>) -> u64 {
+    static WORDS: OnceLock<DigitMatcher> = OnceLock::new();
+    calibration_sum(lines, WORDS.get_or_init(|| DigitMatcher::new(&DIGIT_WORDS)))
+}
 
+pub fn calibration_sum<'a>(
+    lines: impl IntoIterator<Item = &'a str>,
+    matcher: &DigitMatcher,
+) -> u64 {
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| line_value(i + 1, line, matcher))
+        .sum()
+}
+
+// The same sum read a line at a time, so only one line is ever held in memory
+pub fn calibration_sum_reader(mut reader: impl BufRead, matcher: &DigitMatcher) -> Result<u64> {
+    let mut buf = String::new();
     let mut total = 0;
-    if let Ok(lines) = read_lines("input.txt") {
-        for line in lines {
-            if let Ok(line) = line {
-                let mut num = 0;
-                println!("{line}");
-
-                let mut closest_index = 10000;
-                let mut new_line = line.clone();
-                for [letter, letter_num] in letters {
-                    if let Some(index) = line.find(letter) {
-                        if index < closest_index {
-                            closest_index = index;
-                            new_line = line.replace(letter, letter_num);
-                        }
-                    }
-                }
+    let mut line_num = 0;
+    loop {
+        buf.clear();
+        let read = reader
+            .read_line(&mut buf)
+            .map_err(|err| AocError::io(format!("reading line {}", line_num + 1), err))?;
+        if read == 0 {
+            return Ok(total);
+        }
+        line_num += 1;
+        total += line_value(line_num, utils::normalize_line(&buf), matcher);
+    }
+}
 
-                println!("{new_line}");
+fn line_value(line_num: usize, line: &str, matcher: &DigitMatcher) -> u64 {
+    let num = match calibration_value(line, matcher) {
+        Some(num) => num,
+        None => {
+            warn!("line {line_num} has no digits, counting it as 0: {line}");
+            0
+        }
+    };
+    debug!("{line}: {num}");
+    num.into()
+}
 
-                for c in new_line.chars() {
-                    if let Some(n) = c.to_digit(10) {
-                        num = n * 10;
-                        break;
-                    }
-                }
+pub const DIGIT_WORDS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
 
-                let mut closest_index = 0;
-                let mut new_line = line.clone();
-                for [letter, letter_num] in letters {
-                    if let Some(index) = line.rfind(letter) {
-                        if index > closest_index {
-                            closest_index = index;
-                            new_line = line.replace(letter, letter_num);
-                        }
-                    }
-                }
+// The first and last digit of the line as a two digit number. Matches may share letters,
+// e.g. "eightwo" has a first digit of 8 and a last digit of 2
+pub fn calibration_value(line: &str, matcher: &DigitMatcher) -> Option<u32> {
+    let first = matcher.first(line)?;
+    let last = matcher.last(line)?;
+    Some(first.digit * 10 + last.digit)
+}
 
-                println!("{new_line}");
+// How the calibration value of a line was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation<'a> {
+    pub line: &'a str,
+    pub first: Option<Match>,
+    pub last: Option<Match>,
+}
 
-                for c in new_line.chars().rev() {
-                    if let Some(n) = c.to_digit(10) {
-                        num += n;
-                        break;
-                    }
-                }
+impl Explanation<'_> {
+    // None if the line has no digits
+    pub fn value(&self) -> Option<u32> {
+        Some(self.first?.digit * 10 + self.last?.digit)
+    }
+}
+
+pub fn explain<'a>(line: &'a str, matcher: &DigitMatcher) -> Explanation<'a> {
+    Explanation {
+        line,
+        first: matcher.first(line),
+        last: matcher.last(line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate::Random, solution::solve_example};
+
+    #[test]
+    fn part1_example() {
+        let answer = solve_example::<CalibrationDocument>(include_str!("examples/part1.txt"), 1);
+        assert_eq!(answer, Answer::Int(142));
+    }
+
+    #[test]
+    fn part2_example() {
+        let answer = solve_example::<CalibrationDocument>(include_str!("examples/part2.txt"), 2);
+        assert_eq!(answer, Answer::Int(281));
+    }
 
-                total += num;
-                println!("Num: {num}");
+    #[test]
+    fn overlapping_words() {
+        let cases = [
+            ("eightwothree", (8, 3)),
+            ("oneight", (1, 8)),
+            ("twone", (2, 1)),
+            ("sevenine", (7, 9)),
+            ("eighthree", (8, 3)),
+            ("nineight", (9, 8)),
+            ("fiveight", (5, 8)),
+            ("threeight", (3, 8)),
+            ("xtwone3four", (2, 4)),
+            ("zoneight234", (1, 4)),
+            ("1eightwo", (1, 2)),
+            ("twoneight", (2, 8)),
+        ];
+
+        let matcher = DigitMatcher::new(&DIGIT_WORDS);
+        for (line, (first, last)) in cases {
+            assert_eq!(
+                calibration_value(line, &matcher),
+                Some(first * 10 + last),
+                "{line}"
+            );
+        }
+    }
+
+    #[test]
+    fn single_word_is_first_and_last() {
+        let matcher = DigitMatcher::new(&DIGIT_WORDS);
+        assert_eq!(calibration_value("one", &matcher), Some(11));
+        assert_eq!(calibration_value("abcnineabc", &matcher), Some(99));
+        assert_eq!(calibration_value("7", &matcher), Some(77));
+    }
+
+    #[test]
+    fn no_digits() {
+        let matcher = DigitMatcher::new(&DIGIT_WORDS);
+        assert_eq!(calibration_value("abcdef", &matcher), None);
+        assert_eq!(calibration_value("", &matcher), None);
+    }
+
+    #[test]
+    fn parts_share_the_extractor() {
+        let numerals = DigitMatcher::new(&[]);
+        let words = DigitMatcher::new(&DIGIT_WORDS);
+        assert_eq!(calibration_value("two1nine", &numerals), Some(11));
+        assert_eq!(calibration_value("two1nine", &words), Some(29));
+        assert_eq!(calibration_value("eightwothree", &numerals), None);
+        assert_eq!(part1(["1abc2", "treb7uchet"]), 12 + 77);
+        assert_eq!(part2(["1abc2", "eightwothree"]), 12 + 83);
+    }
+
+    // The obvious scan, checking every word at every position
+    fn naive_matches(line: &str, words: &[(&str, u32)]) -> Vec<Match> {
+        let mut matches = vec![];
+        for (start, c) in line.char_indices() {
+            if let Some(digit) = c.to_digit(10) {
+                matches.push(Match {
+                    start,
+                    len: 1,
+                    digit,
+                });
+            }
+            for (word, digit) in words {
+                if line[start..].starts_with(word) {
+                    matches.push(Match {
+                        start,
+                        len: word.len(),
+                        digit: *digit,
+                    });
+                }
             }
         }
+        matches
     }
 
-    println!("Total: {total}");
-}
+    #[test]
+    fn matcher_agrees_with_naive_scan() {
+        let matcher = DigitMatcher::new(&DIGIT_WORDS);
+        let alphabet = b"onetwhrfuivsxg0123456789ab";
+        let mut random = Random::new(0x2023);
+
+        for _ in 0..2000 {
+            let len = random.below(24);
+            let line: String = (0..len)
+                .map(|_| alphabet[random.below(alphabet.len())] as char)
+                .collect();
+
+            let matches = naive_matches(&line, &DIGIT_WORDS);
+            let first = matches
+                .iter()
+                .min_by_key(|m| (m.start, usize::MAX - m.len))
+                .copied();
+            let last = matches.iter().max_by_key(|m| (m.start, m.len)).copied();
+            assert_eq!(matcher.first(&line), first, "{line}");
+            assert_eq!(matcher.last(&line), last, "{line}");
+        }
+    }
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    #[test]
+    fn matcher_reports_offsets() {
+        let matcher = DigitMatcher::new(&DIGIT_WORDS);
+        let line = "xtwone3four";
+        assert_eq!(
+            matcher.first(line),
+            Some(Match {
+                start: 1,
+                len: 3,
+                digit: 2
+            })
+        );
+        assert_eq!(
+            matcher.last(line),
+            Some(Match {
+                start: 7,
+                len: 4,
+                digit: 4
+            })
+        );
+    }
+
+    #[test]
+    fn matcher_prefers_longest_word_at_the_same_start() {
+        let matcher = DigitMatcher::new(&[("un", 1), ("uno", 1), ("dos", 2), ("neun", 9)]);
+        assert_eq!(
+            matcher.first("xneunox"),
+            Some(Match {
+                start: 1,
+                len: 4,
+                digit: 9
+            })
+        );
+        assert_eq!(
+            matcher.last("xneunox"),
+            Some(Match {
+                start: 3,
+                len: 3,
+                digit: 1
+            })
+        );
+    }
+
+    #[test]
+    fn custom_vocabulary() {
+        let german = Vocabulary::new([("null", 0), ("eins", 1), ("zwei", 2), ("fünf", 5)]);
+        let matcher = german.matcher();
+        assert_eq!(calibration_value("xzweiabcfünfy", &matcher), Some(25));
+        assert_eq!(calibration_value("nulleins", &matcher), Some(1));
+        assert_eq!(calibration_value("onetwo", &matcher), None);
+    }
+
+    #[test]
+    fn case_insensitive_vocabulary() {
+        let sensitive = Vocabulary::english().matcher();
+        let insensitive = Vocabulary::english().ignore_case(true).matcher();
+        assert_eq!(calibration_value("xOnEaTWO", &sensitive), None);
+        assert_eq!(calibration_value("xOnEaTWO", &insensitive), Some(12));
+
+        let french = Vocabulary::new([("trois", 3), ("deux", 2), ("zéro", 0)])
+            .ignore_case(true)
+            .matcher();
+        assert_eq!(calibration_value("TROISabcZÉRO", &french), Some(30));
+        assert_eq!(calibration_value("Deux", &french), Some(22));
+    }
+
+    #[test]
+    fn vocabulary_file() {
+        let vocabulary =
+            Vocabulary::parse("ignore_case = true\n\n[words]\ncero = 0\nuno = 1\ndos = 2\n")
+                .unwrap();
+        assert!(vocabulary.ignore_case);
+        assert_eq!(
+            vocabulary.words,
+            vec![
+                ("cero".to_string(), 0),
+                ("dos".to_string(), 2),
+                ("uno".to_string(), 1)
+            ]
+        );
+
+        let err = Vocabulary::parse("[words]\nuno = 1\ndiez = 10\n").unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 3, .. }), "{err}");
+    }
+
+    #[test]
+    fn explain_line() {
+        let matcher = Vocabulary::english().matcher();
+        let explanation = explain("xtwone3four", &matcher);
+        let first = explanation.first.unwrap();
+        let last = explanation.last.unwrap();
+        assert_eq!((first.text("xtwone3four"), first.start), ("two", 1));
+        assert_eq!((last.text("xtwone3four"), last.start), ("four", 7));
+        assert_eq!(explanation.value(), Some(24));
+
+        let explanation = explain("abc", &matcher);
+        assert_eq!(explanation.first, None);
+        assert_eq!(explanation.value(), None);
+    }
+
+    #[test]
+    fn streaming_sum_matches_in_memory_sum() {
+        let matcher = Vocabulary::english().matcher();
+        let contents = include_str!("examples/part2.txt");
+        let expected = calibration_sum(contents.lines(), &matcher);
+        assert_eq!(
+            calibration_sum_reader(contents.as_bytes(), &matcher).unwrap(),
+            expected
+        );
+
+        let crlf = contents.replace('\n', "\r\n");
+        let crlf = crlf.trim_end();
+        assert_eq!(
+            calibration_sum_reader(crlf.as_bytes(), &matcher).unwrap(),
+            expected
+        );
+    }
 }
@@ -0,0 +1,96 @@
+use std::{collections::BTreeMap, path::Path};
+
+use serde::Deserialize;
+
+use crate::{
+    error::{AocError, Result},
+    utils::{load_with, parse_toml},
+};
+
+use super::{DigitMatcher, DIGIT_WORDS};
+
+// The words that count as digits on top of the numerals
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vocabulary {
+    pub words: Vec<(String, u32)>,
+    pub ignore_case: bool,
+}
+
+// A vocabulary file, e.g.
+//
+// ignore_case = true
+//
+// [words]
+// null = 0
+// eins = 1
+// zwei = 2
+#[derive(Deserialize)]
+struct VocabularyFile {
+    #[serde(default)]
+    ignore_case: bool,
+    words: BTreeMap<String, toml::Spanned<u32>>,
+}
+
+impl Default for Vocabulary {
+    fn default() -> Vocabulary {
+        Vocabulary::english()
+    }
+}
+
+impl Vocabulary {
+    pub fn new<S: Into<String>>(words: impl IntoIterator<Item = (S, u32)>) -> Vocabulary {
+        Vocabulary {
+            words: words
+                .into_iter()
+                .map(|(word, n)| (word.into(), n))
+                .collect(),
+            ignore_case: false,
+        }
+    }
+
+    // one to nine
+    pub fn english() -> Vocabulary {
+        Vocabulary::new(DIGIT_WORDS)
+    }
+
+    pub fn ignore_case(mut self, ignore_case: bool) -> Vocabulary {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    pub fn parse(str: &str) -> Result<Vocabulary> {
+        let file: VocabularyFile = parse_toml(str)?;
+
+        let mut words = vec![];
+        for (word, digit) in file.words {
+            if word.is_empty() || *digit.get_ref() > 9 {
+                return Err(AocError::parse_at_offset(
+                    str,
+                    digit.span().start,
+                    format!("{word:?} must be a non empty word for a digit from 0 to 9"),
+                ));
+            }
+            words.push((word, digit.into_inner()));
+        }
+
+        Ok(Vocabulary::new(words).ignore_case(file.ignore_case))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Vocabulary> {
+        load_with(path.as_ref(), Vocabulary::parse)
+    }
+
+    pub fn matcher(&self) -> DigitMatcher {
+        let words: Vec<(&str, u32)> = self
+            .words
+            .iter()
+            .map(|(word, n)| (word.as_str(), *n))
+            .collect();
+
+        if self.ignore_case {
+            DigitMatcher::ignoring_case(&words)
+        } else {
+            DigitMatcher::new(&words)
+        }
+    }
+}
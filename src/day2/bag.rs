@@ -0,0 +1,187 @@
+use std::{fmt::Display, path::Path};
+
+use serde::{
+    de::{self, MapAccess},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    error::Result,
+    utils::{load_with, parse_toml},
+};
+
+use super::Color;
+
+// How many cubes of each color are in the bag, colors that are not listed have none
+#[derive(Debug, Clone, Default)]
+pub struct Bag {
+    // kept in the order the colors were added so the bag prints the way it was written
+    cubes: Vec<(Color, u32)>,
+}
+
+// A color in a round that needs more cubes than the bag has
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    // 1 based
+    pub round: usize,
+    pub color: Color,
+    pub count: u32,
+    pub limit: u32,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "round {} shows {} {}, the bag only has {}",
+            self.round, self.count, self.color, self.limit
+        )
+    }
+}
+
+impl Display for Bag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.cubes.is_empty() {
+            return write!(f, "empty");
+        }
+        for (i, (color, count)) in self.cubes.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{count} {color}")?;
+        }
+        Ok(())
+    }
+}
+
+// Bags are equal if they have the same cubes, whatever order the colors were added in
+impl PartialEq for Bag {
+    fn eq(&self, other: &Bag) -> bool {
+        self.iter().all(|(color, n)| other.limit(color) == n)
+            && other.iter().all(|(color, n)| self.limit(color) == n)
+    }
+}
+
+impl Eq for Bag {}
+
+// As a map from color name to cubes, e.g. {"red": 12, "green": 13}
+impl Serialize for Bag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.cubes.len()))?;
+        for (color, count) in self.cubes.iter() {
+            map.serialize_entry(color, count)?;
+        }
+        map.end()
+    }
+}
+
+// The colors of a bag file in the order they are written
+struct BagFile(Vec<(String, u32)>);
+
+impl<'de> Deserialize<'de> for BagFile {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<BagFile, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = BagFile;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a table of colors and their number of cubes")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<BagFile, A::Error> {
+                let mut cubes = vec![];
+                while let Some(entry) = map.next_entry()? {
+                    cubes.push(entry);
+                }
+                Ok(BagFile(cubes))
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
+
+impl Bag {
+    pub fn new<'a>(cubes: impl IntoIterator<Item = (&'a str, u32)>) -> Bag {
+        let mut bag = Bag::default();
+        for (name, count) in cubes {
+            bag.set(Color::named(name), count);
+        }
+        bag
+    }
+
+    // The bag part 1 asks about: 12 red, 13 green and 14 blue cubes
+    pub fn puzzle() -> Bag {
+        Bag::new([("red", 12), ("green", 13), ("blue", 14)])
+    }
+
+    pub fn limit(&self, color: &Color) -> u32 {
+        self.cubes
+            .iter()
+            .find(|(c, _)| c == color)
+            .map(|(_, count)| *count)
+            .unwrap_or(0)
+    }
+
+    pub fn set(&mut self, color: Color, count: u32) {
+        match self.cubes.iter_mut().find(|(c, _)| *c == color) {
+            Some((_, old)) => *old = count,
+            None => self.cubes.push((color, count)),
+        }
+    }
+
+    // The product of the cubes of every color, None if it overflows
+    pub fn power(&self) -> Option<u64> {
+        self.iter()
+            .try_fold(1u64, |power, (_, n)| power.checked_mul(n.into()))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Color, u32)> + '_ {
+        self.cubes.iter().map(|(color, count)| (color, *count))
+    }
+
+    // A bag file maps each color to its number of cubes, e.g.
+    //
+    // red = 12
+    // green = 13
+    // blue = 14
+    // yellow = 3
+    pub fn parse(str: &str) -> Result<Bag> {
+        let file: BagFile = parse_toml(str)?;
+        Ok(Bag::new(
+            file.0.iter().map(|(name, count)| (name.as_str(), *count)),
+        ))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Bag> {
+        load_with(path.as_ref(), Bag::parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AocError;
+
+    #[test]
+    fn bag_file() {
+        let bag = Bag::parse("red = 1\nyellow = 2\n").unwrap();
+        assert_eq!(bag.limit(&Color::red()), 1);
+        assert_eq!(bag.limit(&Color::named("yellow")), 2);
+        assert_eq!(bag.limit(&Color::blue()), 0);
+
+        // colors keep the order of the file
+        let bag = Bag::parse("green = 1\nred = 2\nblue = 3\n").unwrap();
+        assert_eq!(bag.to_string(), "1 green, 2 red, 3 blue");
+
+        let err = Bag::parse("red = 1\npink = -2\n").unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 2, .. }), "{err}");
+    }
+}
@@ -0,0 +1,66 @@
+use std::{collections::HashSet, fmt::Display, sync::Arc};
+
+use serde::{Serialize, Serializer};
+
+// A cube color, any word in a game log is a color. Colors compare by name
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Color(Arc<str>);
+
+impl Color {
+    pub fn named(name: &str) -> Color {
+        Color(name.into())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    pub fn red() -> Color {
+        Color::named("red")
+    }
+
+    pub fn green() -> Color {
+        Color::named("green")
+    }
+
+    pub fn blue() -> Color {
+        Color::named("blue")
+    }
+}
+
+// The colors of one game log, so a log with millions of draws stores each name once. Owned
+// by the parser and dropped with it
+#[derive(Default)]
+pub struct Colors {
+    known: HashSet<Arc<str>>,
+}
+
+impl Colors {
+    pub fn intern(&mut self, name: &str) -> Color {
+        if let Some(known) = self.known.get(name) {
+            return Color(known.clone());
+        }
+        let name: Arc<str> = name.into();
+        self.known.insert(name.clone());
+        Color(name)
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // pad so colors line up in tables
+        f.pad(self.name())
+    }
+}
+
+impl std::fmt::Debug for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
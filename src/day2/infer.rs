@@ -0,0 +1,396 @@
+// Which bags could have produced a game log. Every round draws its cubes from the full bag
+// without replacement and the cubes go back in before the next round, so the chance of a
+// round is the multivariate hypergeometric probability
+//
+//   C(n_1, k_1) * ... * C(n_c, k_c) / C(n_1 + ... + n_c, k_1 + ... + k_c)
+//
+// for n_i cubes of color i in the bag and k_i of them drawn.
+//
+// There is usually no most likely bag. A round that draws several cubes of one color gets
+// more likely the bigger the bag, as each cube drawn takes less away from the rest, so on
+// most logs the likelihood keeps rising without end and the search box decides the answer.
+
+use serde::Serialize;
+
+use super::{Bag, Color, GameLog};
+
+// The most bags `rank` will score, past this it takes too long to be useful
+pub const MAX_CANDIDATES: u64 = 1_000_000;
+
+// The most extra cubes of each color `most_likely` is meant to search. It takes a step per
+// cube and each step goes over every round
+pub const MAX_EXTRA: u32 = 10_000;
+
+pub struct Inference {
+    colors: Vec<Color>,
+    // the cubes drawn in each round, in the order of `colors`
+    rounds: Vec<Vec<u32>>,
+    // the fewest cubes of each color that can produce the log, in the order of `colors`
+    min: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Candidate {
+    pub bag: Bag,
+    // natural log of the chance of every round in the log
+    pub log_likelihood: f64,
+}
+
+// The most likely bag a search found within `extra` cubes of the minimum
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Optimum {
+    pub best: Candidate,
+    // colors the search stopped at the edge of the box for, where one more cube would be
+    // more likely still. Empty if the likelihood falls in every direction
+    pub on_edge: Vec<Color>,
+}
+
+impl Inference {
+    pub fn new(log: &GameLog) -> Inference {
+        let colors = log.colors();
+        let rounds: Vec<Vec<u32>> = log
+            .games()
+            .iter()
+            .flat_map(|game| game.rounds.iter())
+            .map(|round| {
+                colors
+                    .iter()
+                    .map(|color| round.get_count_for_color(color))
+                    .collect()
+            })
+            .collect();
+        let min = (0..colors.len())
+            .map(|i| rounds.iter().map(|round| round[i]).max().unwrap_or(0))
+            .collect();
+
+        Inference {
+            colors,
+            rounds,
+            min,
+        }
+    }
+
+    // The feasible region is every bag with at least this many cubes of each color, as a
+    // round can't show more cubes of a color than the bag holds
+    pub fn min_bag(&self) -> Bag {
+        self.bag(&self.min)
+    }
+
+    pub fn is_feasible(&self, bag: &Bag) -> bool {
+        self.colors
+            .iter()
+            .zip(self.min.iter())
+            .all(|(color, min)| bag.limit(color) >= *min)
+    }
+
+    // None if the bag can't have produced the log
+    pub fn log_likelihood(&self, bag: &Bag) -> Option<f64> {
+        if !self.is_feasible(bag) {
+            return None;
+        }
+
+        let cubes: Vec<u32> = self.colors.iter().map(|color| bag.limit(color)).collect();
+        // colors that never show up still make the bag bigger
+        let total: u64 = bag.iter().map(|(_, n)| u64::from(n)).sum();
+
+        let mut log_likelihood = 0.0;
+        for round in self.rounds.iter() {
+            let drawn: u64 = round.iter().map(|k| u64::from(*k)).sum();
+            for (n, k) in cubes.iter().zip(round.iter()) {
+                log_likelihood += ln_choose((*n).into(), (*k).into());
+            }
+            log_likelihood -= ln_choose(total, drawn);
+        }
+        Some(log_likelihood)
+    }
+
+    // How many bags `rank` scores for `extra`, (extra + 1) ^ colors
+    pub fn candidates(&self, extra: u32) -> u64 {
+        let side = u64::from(extra) + 1;
+        (0..self.colors.len()).fold(1u64, |count, _| count.saturating_mul(side))
+    }
+
+    // Every bag with up to `extra` more cubes of each color than the minimum, most likely first.
+    // None if there are more than MAX_CANDIDATES of them
+    pub fn rank(&self, extra: u32) -> Option<Vec<Candidate>> {
+        if self.candidates(extra) > MAX_CANDIDATES {
+            return None;
+        }
+
+        // The likelihood splits into a sum per color over the cubes of that color and a sum
+        // over the size of the whole bag, so score each once and add them up per bag
+        let per_color: Vec<Vec<f64>> = (0..self.colors.len())
+            .map(|i| {
+                (0..=extra)
+                    .map(|offset| {
+                        let n = u64::from(self.min[i]) + u64::from(offset);
+                        self.rounds
+                            .iter()
+                            .map(|round| ln_choose(n, round[i].into()))
+                            .sum()
+                    })
+                    .collect()
+            })
+            .collect();
+        let min_total: u64 = self.min.iter().map(|n| u64::from(*n)).sum();
+        let drawn: Vec<u64> = self
+            .rounds
+            .iter()
+            .map(|round| round.iter().map(|k| u64::from(*k)).sum())
+            .collect();
+        let per_total: Vec<f64> = (0..=self.colors.len() as u64 * u64::from(extra))
+            .map(|offset| {
+                drawn
+                    .iter()
+                    .map(|k| ln_choose(min_total + offset, *k))
+                    .sum()
+            })
+            .collect();
+
+        let mut offsets = vec![0u32; self.colors.len()];
+        let mut candidates = vec![];
+        loop {
+            let total: u64 = offsets.iter().map(|offset| u64::from(*offset)).sum();
+            let log_likelihood = offsets
+                .iter()
+                .enumerate()
+                .map(|(i, offset)| per_color[i][*offset as usize])
+                .sum::<f64>()
+                - per_total[total as usize];
+            let cubes: Vec<u32> = self
+                .min
+                .iter()
+                .zip(offsets.iter())
+                .map(|(min, offset)| min + offset)
+                .collect();
+            candidates.push(Candidate {
+                bag: self.bag(&cubes),
+                log_likelihood,
+            });
+
+            // count up through the offsets like the digits of a number in base extra + 1
+            let Some(i) = offsets.iter().position(|offset| *offset < extra) else {
+                break;
+            };
+            offsets[i] += 1;
+            offsets[..i].fill(0);
+        }
+
+        candidates.sort_by(|a, b| b.log_likelihood.total_cmp(&a.log_likelihood));
+        Some(candidates)
+    }
+
+    // The most likely bag with up to `extra` more cubes of each color than the minimum,
+    // without scoring every one. For a bag of a given size the colors don't interact, and
+    // each color's part of the likelihood gains less with every cube it gets. So handing out
+    // cubes one at a time to the color that gains most passes through the most likely bag of
+    // every size, and only the sizes are left to compare. Takes colors * extra steps of one
+    // pass over the rounds each
+    pub fn most_likely(&self, extra: u32) -> Optimum {
+        let max: Vec<u32> = self
+            .min
+            .iter()
+            .map(|min| min.saturating_add(extra))
+            .collect();
+        let score = |cubes: &[u32]| {
+            self.log_likelihood(&self.bag(cubes))
+                .expect("bags from the minimum up are feasible")
+        };
+        let color_gain = |i: usize, n: u32| -> f64 {
+            self.rounds
+                .iter()
+                .map(|round| ln_choose_gain(n.into(), round[i].into()))
+                .sum()
+        };
+        let drawn: Vec<u64> = self
+            .rounds
+            .iter()
+            .map(|round| round.iter().map(|k| u64::from(*k)).sum())
+            .collect();
+
+        let mut cubes = self.min.clone();
+        let mut total: u64 = cubes.iter().map(|n| u64::from(*n)).sum();
+        let mut gains: Vec<f64> = (0..cubes.len()).map(|i| color_gain(i, cubes[i])).collect();
+        let mut log_likelihood = score(&cubes);
+        let mut best = (cubes.clone(), log_likelihood);
+        while let Some(i) = (0..cubes.len())
+            .filter(|i| cubes[*i] < max[*i])
+            .max_by(|a, b| gains[*a].total_cmp(&gains[*b]))
+        {
+            let size_gain: f64 = drawn.iter().map(|k| ln_choose_gain(total, *k)).sum();
+            log_likelihood += gains[i] - size_gain;
+            cubes[i] += 1;
+            total += 1;
+            gains[i] = color_gain(i, cubes[i]);
+            if log_likelihood > best.1 {
+                best = (cubes.clone(), log_likelihood);
+            }
+        }
+        let (cubes, _) = best;
+        // score it again so the steps' rounding doesn't add up
+        let best = score(&cubes);
+
+        // the colors a more likely bag just past the box needs more of
+        let at_edge: Vec<usize> = (0..cubes.len())
+            .filter(|i| cubes[*i] == max[*i] && cubes[*i] < u32::MAX)
+            .collect();
+        let mut on_edge: Vec<usize> = at_edge
+            .iter()
+            .copied()
+            .filter(|i| {
+                let mut next = cubes.clone();
+                next[*i] += 1;
+                score(&next) > best
+            })
+            .collect();
+        // more of every color keeps their mix, which one color alone can't
+        let more: Vec<u32> = cubes.iter().map(|n| n.saturating_add(1)).collect();
+        if on_edge.is_empty() && !at_edge.is_empty() && score(&more) > best {
+            on_edge = at_edge;
+        }
+
+        Optimum {
+            best: Candidate {
+                bag: self.bag(&cubes),
+                log_likelihood: best,
+            },
+            on_edge: on_edge
+                .into_iter()
+                .map(|i| self.colors[i].clone())
+                .collect(),
+        }
+    }
+
+    fn bag(&self, cubes: &[u32]) -> Bag {
+        let mut bag = Bag::default();
+        for (color, n) in self.colors.iter().zip(cubes.iter()) {
+            bag.set(color.clone(), *n);
+        }
+        bag
+    }
+}
+
+// ln C(n, k) without a table, so huge bags cost no more than small ones. Small k are summed
+// exactly and the rest go through Stirling's series
+fn ln_choose(n: u64, k: u64) -> f64 {
+    let k = k.min(n - k);
+    if k < 64 {
+        (0..k).map(|i| ((n - i) as f64 / (i + 1) as f64).ln()).sum()
+    } else {
+        ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k)
+    }
+}
+
+// ln C(n + 1, k) - ln C(n, k), what one more cube adds
+fn ln_choose_gain(n: u64, k: u64) -> f64 {
+    ((n + 1) as f64 / (n + 1 - k) as f64).ln()
+}
+
+// Stirling's series for ln(n!), good to double precision from n = 64 up
+fn ln_factorial(n: u64) -> f64 {
+    let n = n as f64;
+    n * n.ln() - n + 0.5 * (2.0 * std::f64::consts::PI * n).ln() + 1.0 / (12.0 * n)
+        - 1.0 / (360.0 * n.powi(3))
+        + 1.0 / (1260.0 * n.powi(5))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day2::one_game, solution::Solution};
+
+    #[test]
+    fn inference() {
+        let log = GameLog::parse(include_str!("examples/example.txt")).unwrap();
+        let inference = Inference::new(&log);
+        assert_eq!(
+            inference.min_bag(),
+            Bag::new([("red", 20), ("green", 13), ("blue", 15)])
+        );
+        assert!(!inference.is_feasible(&Bag::puzzle()));
+        assert_eq!(inference.log_likelihood(&Bag::puzzle()), None);
+        assert!(inference.is_feasible(&Bag::new([("red", 21), ("green", 13), ("blue", 15)])));
+
+        let candidates = inference.rank(2).unwrap();
+        assert_eq!(candidates.len(), 27);
+        assert!(candidates
+            .windows(2)
+            .all(|pair| pair[0].log_likelihood >= pair[1].log_likelihood));
+    }
+
+    #[test]
+    fn likelihood_of_drawing_without_replacement() {
+        let log = GameLog::parse("Game 1: 1 red, 1 blue\n").unwrap();
+        let inference = Inference::new(&log);
+
+        // drawing both cubes of a two cube bag is certain
+        let best = &inference.rank(3).unwrap()[0];
+        assert_eq!(best.bag, Bag::new([("red", 1), ("blue", 1)]));
+        assert_eq!(best.log_likelihood, 0.0);
+
+        // 2 * 1 ways to draw a red and a blue out of C(3, 2) = 3
+        let ll = inference
+            .log_likelihood(&Bag::new([("red", 2), ("blue", 1)]))
+            .unwrap();
+        assert!((ll - (2.0f64 / 3.0).ln()).abs() < 1e-9);
+
+        // a yellow cube that is never drawn makes the round less likely
+        let ll = inference
+            .log_likelihood(&Bag::new([("red", 1), ("blue", 1), ("yellow", 1)]))
+            .unwrap();
+        assert!((ll - (1.0f64 / 3.0).ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn most_likely_bag_inside_the_box() {
+        let log =
+            GameLog::parse("Game 1: 3 red; 1 red, 2 blue; 2 red, 1 blue\nGame 2: 1 red, 1 blue\n")
+                .unwrap();
+        let inference = Inference::new(&log);
+        let optimum = inference.most_likely(20);
+        assert_eq!(optimum.best.bag, Bag::new([("red", 7), ("blue", 4)]));
+        assert!(optimum.on_edge.is_empty());
+        assert!(
+            (optimum.best.log_likelihood - inference.rank(20).unwrap()[0].log_likelihood).abs()
+                < 1e-9
+        );
+        // a bigger box finds the same bag
+        assert_eq!(inference.most_likely(1000), optimum);
+
+        // with the same mix, bigger bags only ever make these rounds more likely
+        let log = GameLog::parse("Game 1: 2 red; 2 blue; 1 red, 1 blue\n").unwrap();
+        let optimum = Inference::new(&log).most_likely(20);
+        assert_eq!(optimum.best.bag, Bag::new([("red", 22), ("blue", 22)]));
+        assert_eq!(optimum.on_edge, vec![Color::red(), Color::blue()]);
+    }
+
+    #[test]
+    fn inference_limits() {
+        // big bags don't need memory for their size
+        let log = GameLog::parse("Game 1: 1 red\n").unwrap();
+        let inference = Inference::new(&log);
+        let ll = inference
+            .log_likelihood(&Bag::new([("red", 3_000_000_000), ("blue", 1)]))
+            .unwrap();
+        assert!((ll - (3e9f64 / 3_000_000_001.0).ln()).abs() < 1e-12);
+
+        // large draws against ln C(n, k) summed term by term
+        let ln_choose = |n: u32, k: u32| -> f64 {
+            (0..k).map(|i| ((n - i) as f64 / (i + 1) as f64).ln()).sum()
+        };
+        let log = GameLog::parse("Game 1: 100 red, 100 blue\n").unwrap();
+        let ll = Inference::new(&log)
+            .log_likelihood(&Bag::new([("red", 150), ("blue", 150)]))
+            .unwrap();
+        let exact = 2.0 * ln_choose(150, 100) - ln_choose(300, 200);
+        assert!((ll - exact).abs() < 1e-9);
+
+        // eight colors with extra 10 is too many bags to rank, but not to search
+        let names: Vec<String> = (0..8).map(|i| format!("c{i}")).collect();
+        let draws: Vec<(u32, &str)> = (1..).zip(names.iter().map(String::as_str)).collect();
+        let inference = Inference::new(&one_game(&draws));
+        assert_eq!(inference.candidates(10), 11u64.pow(8));
+        assert!(inference.rank(10).is_none());
+        assert_eq!(inference.most_likely(10).best.bag, inference.min_bag());
+    }
+}
@@ -1,152 +1,410 @@
-use regex::Regex;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::str::FromStr;
-
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Color {
-    Red,
-    Blue,
-    Green,
-}
+mod bag;
+mod color;
+mod infer;
+mod parse_error;
+mod report;
 
-impl FromStr for Color {
-    type Err = ();
+use log::warn;
 
-    fn from_str(input: &str) -> Result<Color, Self::Err> {
-        match input {
-            "red" => Ok(Color::Red),
-            "blue" => Ok(Color::Blue),
-            "green" => Ok(Color::Green),
-            _ => Err(()),
-        }
-    }
-}
+pub use bag::{Bag, Violation};
+pub use color::{Color, Colors};
+pub use infer::{Candidate, Inference, Optimum, MAX_CANDIDATES, MAX_EXTRA};
+pub use parse_error::GameParseError;
+pub use report::{ColorStats, GameReport, Report};
 
-struct ColorCount {
-    amount: u32,
-    color: Color,
+use crate::{
+    error::{AocError, Result},
+    solution::{Answer, Solution},
+    utils,
+};
+
+pub struct ColorCount {
+    pub amount: u32,
+    pub color: Color,
 }
 
-struct Round {
-    counts: Vec<ColorCount>,
+pub struct Round {
+    pub counts: Vec<ColorCount>,
 }
 
 impl Round {
-    pub fn get_count_for_color(&self, color: Color) -> u32 {
+    pub fn get_count_for_color(&self, color: &Color) -> u32 {
         self.counts
             .iter()
-            .find(|count| count.color == color)
+            .find(|count| count.color == *color)
             .map(|count| count.amount)
             .unwrap_or(0)
     }
 }
 
-struct Game {
-    id: String,
-    rounds: Vec<Round>,
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Round>,
 }
 
 impl Game {
-    pub fn get_largest_count_for_color(&self, color: Color) -> u32 {
+    pub fn get_largest_count_for_color(&self, color: &Color) -> u32 {
         self.rounds
             .iter()
             .map(|round| round.get_count_for_color(color))
             .max()
             .unwrap_or(0)
     }
+
+    // The fewest cubes of each of `colors` that make the game possible
+    pub fn min_bag(&self, colors: &[Color]) -> Bag {
+        let mut bag = Bag::default();
+        for color in colors.iter() {
+            bag.set(color.clone(), self.get_largest_count_for_color(color));
+        }
+        bag
+    }
+
+    // Every color the game draws, in the order they first show up
+    pub fn colors(&self) -> Vec<Color> {
+        let mut colors: Vec<Color> = vec![];
+        for count in self.rounds.iter().flat_map(|round| round.counts.iter()) {
+            if !colors.contains(&count.color) {
+                colors.push(count.color.clone());
+            }
+        }
+        colors
+    }
+
+    // The power of the smallest bag of `colors` for the game, None if it overflows. A color
+    // the game never draws needs no cubes and makes the power 0
+    pub fn power(&self, colors: &[Color]) -> Option<u64> {
+        self.min_bag(colors).power()
+    }
+
+    // Every color of every round that needs more cubes than the bag has, empty if the game
+    // is possible
+    pub fn is_possible_with(&self, bag: &Bag) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (i, round) in self.rounds.iter().enumerate() {
+            for count in round.counts.iter() {
+                let limit = bag.limit(&count.color);
+                if count.amount > limit {
+                    violations.push(Violation {
+                        round: i + 1,
+                        color: count.color.clone(),
+                        count: count.amount,
+                        limit,
+                    });
+                }
+            }
+        }
+        violations
+    }
 }
 
 impl Game {
-    fn parse_line(line: String) -> Option<Game> {
-        let re = Regex::new(r"Game (\d+): (.*)").unwrap();
-        let color_re = Regex::new(r"(\d+) (\w+)").unwrap();
+    // line_num is only used for error messages, color names are interned in `colors`
+    pub fn parse_line(
+        line_num: usize,
+        line: &str,
+        colors: &mut Colors,
+    ) -> std::result::Result<Game, GameParseError> {
+        let error = |token: &str, expected| GameParseError::new(line_num, line, token, expected);
+        let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
 
-        let caps = re.captures(&line)?;
-        let id = caps.get(1)?.as_str().to_string();
-        let rounds_data = caps.get(2)?.as_str();
+        let header = line.split(':').next().unwrap_or(line);
+        let (id, rounds_data) = line
+            .strip_prefix("Game ")
+            .and_then(|rest| rest.split_once(':'))
+            .filter(|(id, _)| is_number(id))
+            .ok_or_else(|| error(header, "a header like \"Game 1:\""))?;
+        let id = id
+            .parse()
+            .map_err(|_| error(id, "a game id that fits in 32 bits"))?;
 
-        let rounds: Vec<Round> = rounds_data
-            .split(';')
-            .map(|round_data| {
-                let counts = color_re
-                    .captures_iter(round_data)
-                    .filter_map(|cap| {
-                        let amount = cap[1].parse().ok()?;
-                        let color = Color::from_str(&cap[2]).ok()?;
+        let mut rounds = vec![];
+        for round_data in rounds_data.split(';') {
+            let mut counts = vec![];
+            for count in round_data.split(',') {
+                let count = count.trim();
+                let (amount, color) = match count.split_once(' ') {
+                    Some(count) => count,
+                    None if is_number(count) => {
+                        return Err(error(&count[count.len()..], "a color like \"blue\""))
+                    }
+                    None => return Err(error(count, "a count like \"3 blue\"")),
+                };
+                if !is_number(amount) {
+                    return Err(error(amount, "a number of cubes"));
+                }
+                if color.is_empty() || !color.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    return Err(error(color, "a color like \"blue\""));
+                }
 
-                        Some(ColorCount { amount, color })
-                    })
-                    .collect();
-
-                Round { counts }
-            })
-            .collect();
+                counts.push(ColorCount {
+                    amount: amount
+                        .parse()
+                        .map_err(|_| error(amount, "a number of cubes that fits in 32 bits"))?,
+                    color: colors.intern(color),
+                });
+            }
+            rounds.push(Round { counts });
+        }
 
-        Some(Game { id, rounds })
+        Ok(Game { id, rounds })
     }
 }
 
-pub fn part1() {
-    fn is_valid_game(game: &Game) -> bool {
-        let red_limit = 12;
-        let green_limit = 13;
-        let blue_limit = 14;
-        for round in game.rounds.iter() {
-            let red_count = round.get_count_for_color(Color::Red);
-            let green_count = round.get_count_for_color(Color::Green);
-            let blue_count = round.get_count_for_color(Color::Blue);
-
-            if red_count > red_limit || green_count > green_limit || blue_count > blue_limit {
-                return false;
+pub struct GameLog {
+    games: Vec<Game>,
+}
+
+impl GameLog {
+    pub fn games(&self) -> &[Game] {
+        &self.games
+    }
+
+    // Every color in the log in the order they first show up
+    pub fn colors(&self) -> Vec<Color> {
+        let mut colors: Vec<Color> = vec![];
+        for round in self.games.iter().flat_map(|game| game.rounds.iter()) {
+            for count in round.counts.iter() {
+                if !colors.contains(&count.color) {
+                    colors.push(count.color.clone());
+                }
             }
         }
-        return true;
+        colors
     }
 
-    if let Ok(lines) = read_lines("src/day2/input.txt") {
-        let games = lines
-            .filter_map(|line| line.ok())
-            .filter_map(Game::parse_line)
-            .filter(is_valid_game);
+    // The colors part 2 takes powers over: every color in the log, and red, green and blue
+    // even if no game draws them
+    pub fn power_colors(&self) -> Vec<Color> {
+        let mut colors = self.colors();
+        for color in [Color::red(), Color::green(), Color::blue()] {
+            if !colors.contains(&color) {
+                colors.push(color);
+            }
+        }
+        colors
+    }
+
+    // Sum of the ids of the games that are possible with the bag
+    pub fn possible_id_sum(&self, bag: &Bag) -> u32 {
+        self.games
+            .iter()
+            .filter(|game| game.is_possible_with(bag).is_empty())
+            .map(|game| game.id)
+            .sum()
+    }
+}
 
-        let id_sum = games
-            .map(|game| game.id.parse::<u32>().unwrap())
-            .sum::<u32>();
+impl GameLog {
+    // Malformed lines are skipped with a warning, unless `strict` where the first one is
+    // returned as the error
+    pub fn parse_with(input: &str, strict: bool) -> std::result::Result<GameLog, GameParseError> {
+        let mut games = vec![];
+        let mut colors = Colors::default();
+        for (line_num, line) in utils::lines(input).filter(|(_, line)| !line.is_empty()) {
+            match Game::parse_line(line_num, line, &mut colors) {
+                Ok(game) => games.push(game),
+                Err(err) if strict => return Err(err),
+                Err(err) => warn!("skipping malformed game\n{}", err.render()),
+            }
+        }
 
-        println!("Sum of valid game IDs: {}", id_sum);
-    } else {
-        println!("Could not read input file");
+        Ok(GameLog { games })
     }
 }
 
-pub fn part2() {
-    fn smallest_set(game: Game) -> u32 {
-        let smallest_red = game.get_largest_count_for_color(Color::Red);
-        let smallest_green = game.get_largest_count_for_color(Color::Green);
-        let smallest_blue = game.get_largest_count_for_color(Color::Blue);
+impl Solution for GameLog {
+    fn parse(input: &str) -> Result<GameLog> {
+        Ok(GameLog::parse_with(input, false)?)
+    }
+
+    fn parse_strict(input: &str) -> Result<GameLog> {
+        Ok(GameLog::parse_with(input, true)?)
+    }
 
-        return smallest_red * smallest_blue * smallest_green;
+    fn part1(&self) -> Result<Answer> {
+        Ok(self.possible_id_sum(&Bag::puzzle()).into())
     }
 
-    if let Ok(lines) = read_lines("src/day2/input.txt") {
-        let sum = lines
-            .filter_map(|line| line.ok())
-            .filter_map(Game::parse_line)
-            .map(smallest_set)
-            .sum::<u32>();
+    fn part2(&self) -> Result<Answer> {
+        let colors = self.power_colors();
+        let mut sum = 0u64;
+        for game in self.games.iter() {
+            sum = game
+                .power(&colors)
+                .and_then(|power| sum.checked_add(power))
+                .ok_or_else(|| {
+                    AocError::no_answer(format!("the powers overflow at game {}", game.id))
+                })?;
+        }
 
-        println!("Answer: {}", sum);
-    } else {
-        println!("Could not read input file");
+        sum.try_into()
     }
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+// A log of one game with a single round of `draws`
+#[cfg(test)]
+fn one_game(draws: &[(u32, &str)]) -> GameLog {
+    let draws: Vec<String> = draws
+        .iter()
+        .map(|(amount, color)| format!("{amount} {color}"))
+        .collect();
+    GameLog::parse(&format!("Game 1: {}\n", draws.join(", "))).expect("one game should parse")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_example;
+
+    #[test]
+    fn part1_example() {
+        let answer = solve_example::<GameLog>(include_str!("examples/example.txt"), 1);
+        assert_eq!(answer, Answer::Int(8));
+    }
+
+    #[test]
+    fn part2_example() {
+        let answer = solve_example::<GameLog>(include_str!("examples/example.txt"), 2);
+        assert_eq!(answer, Answer::Int(2286));
+    }
+
+    #[test]
+    fn violations_name_the_round_and_color() {
+        let log = GameLog::parse(include_str!("examples/example.txt")).unwrap();
+        let game3 = &log.games()[2];
+        assert_eq!(
+            game3.is_possible_with(&Bag::puzzle()),
+            vec![Violation {
+                round: 1,
+                color: Color::red(),
+                count: 20,
+                limit: 12
+            },]
+        );
+        assert!(log.games()[0].is_possible_with(&Bag::puzzle()).is_empty());
+
+        let bag = Bag::parse("red = 20\ngreen = 13\nblue = 6\n").unwrap();
+        assert!(game3.is_possible_with(&bag).is_empty());
+        assert_eq!(log.possible_id_sum(&bag), 1 + 2 + 3 + 5);
+    }
+
+    #[test]
+    fn any_color() {
+        let log = GameLog::parse(
+            "Game 1: 3 yellow, 4 red; 2 purple\nGame 2: 1 yellow, 2 purple, 5 red; 7 yellow\n",
+        )
+        .unwrap();
+        assert_eq!(
+            log.colors(),
+            vec![Color::named("yellow"), Color::red(), Color::named("purple")]
+        );
+        let colors = log.colors();
+        let powers: Vec<_> = log.games().iter().map(|game| game.power(&colors)).collect();
+        assert_eq!(powers, vec![Some(3 * 4 * 2), Some(7 * 5 * 2)]);
+        // part 2 always takes green and blue, which these games need none of
+        assert_eq!(log.part2().unwrap(), Answer::Int(0));
+
+        let bag = Bag::new([("yellow", 5), ("red", 5), ("purple", 2)]);
+        assert_eq!(log.possible_id_sum(&bag), 1);
+        assert_eq!(
+            log.games()[1].is_possible_with(&bag),
+            vec![Violation {
+                round: 2,
+                color: Color::named("yellow"),
+                count: 7,
+                limit: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_bad_token() {
+        let cases = [
+            ("Gme 1: 3 blue", 0..5, "a header like \"Game 1:\""),
+            ("Game x: 3 blue", 0..6, "a header like \"Game 1:\""),
+            ("Game 1: x blue", 8..9, "a number of cubes"),
+            ("Game 1: 3 blue; 4", 17..17, "a color like \"blue\""),
+            ("Game 1: 3 blue, 4 bl-ue", 18..23, "a color like \"blue\""),
+            ("Game 1: 3 blue,, 4 red", 15..15, "a count like \"3 blue\""),
+            (
+                "Game 99999999999: 3 blue",
+                5..16,
+                "a game id that fits in 32 bits",
+            ),
+        ];
+        for (line, span, expected) in cases {
+            let err = Game::parse_line(7, line, &mut Colors::default())
+                .err()
+                .unwrap();
+            assert_eq!(
+                (err.line, err.span, err.expected),
+                (7, span, expected),
+                "{line}"
+            );
+        }
+    }
+
+    #[test]
+    fn strict_mode_rejects_malformed_lines() {
+        let input = "Game 1: 3 blue\nGame 2: 3 purple, x red\nGame 3: 1 red\n";
+        let lenient = GameLog::parse_with(input, false).unwrap();
+        assert_eq!(lenient.games().len(), 2);
+        assert!(GameLog::parse(input).is_ok());
+        assert!(matches!(
+            GameLog::parse_strict(input),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+
+        let err = GameLog::parse_with(input, true).err().unwrap();
+        assert_eq!(
+            err.render(),
+            "line 2: expected a number of cubes, found \"x\"\n   \
+             2 | Game 2: 3 purple, x red\n     |                   ^"
+        );
+        assert!(matches!(
+            AocError::from(err),
+            AocError::Parse {
+                line: 2,
+                column: 19,
+                ..
+            }
+        ));
+
+        // both renderings underline the whole token
+        let input = "Game 1: 3 blue, 4 bl-ue\n";
+        let err = GameLog::parse_with(input, true).err().unwrap();
+        let underline = format!("\n     | {}^^^^^", " ".repeat(18));
+        assert!(err.render().ends_with(&underline), "{}", err.render());
+        let err = AocError::from(err);
+        assert!(
+            err.render(input).ends_with(&underline),
+            "{}",
+            err.render(input)
+        );
+    }
+
+    #[test]
+    fn power_overflow_is_an_error() {
+        let colors = ["red", "green", "blue", "d", "e", "f", "g", "h"];
+        let log = one_game(&colors.map(|color| (20, color)));
+        assert_eq!(log.games()[0].power(&log.colors()), Some(20u64.pow(8)));
+        assert_eq!(log.part2().unwrap(), Answer::Int(20i64.pow(8)));
+        assert_eq!(Report::new(&log).games[0].power, Some(20u64.pow(8)));
+
+        let log = one_game(&[
+            (4_000_000_000, "red"),
+            (4_000_000_000, "green"),
+            (4_000_000_000, "blue"),
+        ]);
+        assert_eq!(log.games()[0].power(&log.colors()), None);
+        assert!(matches!(log.part2(), Err(AocError::NoAnswer(_))));
+        let report = Report::new(&log);
+        assert_eq!(report.games[0].power, None);
+        assert!(serde_json::to_value(&report).unwrap()["games"][0]["power"].is_null());
+
+        // fits in the u64 sum but not in an answer
+        let log = one_game(&[(4294967295, "red"), (4294967295, "blue"), (1, "green")]);
+        assert!(matches!(log.part2(), Err(AocError::NoAnswer(_))));
+    }
 }
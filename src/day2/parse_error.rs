@@ -0,0 +1,78 @@
+use std::{fmt::Display, ops::Range};
+
+use crate::error::{underline, AocError};
+
+// A game line that could not be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameParseError {
+    // 1 based
+    pub line: usize,
+    // byte range of the offending text within the line
+    pub span: Range<usize>,
+    pub expected: &'static str,
+    pub text: String,
+}
+
+impl GameParseError {
+    // `token` must be a slice of `line`
+    pub fn new(line_num: usize, line: &str, token: &str, expected: &'static str) -> GameParseError {
+        let start = (token.as_ptr() as usize)
+            .saturating_sub(line.as_ptr() as usize)
+            .min(line.len());
+        GameParseError {
+            line: line_num,
+            span: start..start + token.len(),
+            expected,
+            text: line.to_string(),
+        }
+    }
+
+    pub fn found(&self) -> &str {
+        &self.text[self.span.clone()]
+    }
+
+    // The error followed by the line with the offending text underlined, e.g.
+    //
+    // line 3: expected a number of cubes, found "x"
+    //     3 | Game 3: x blue, 4 red
+    //       |         ^
+    pub fn render(&self) -> String {
+        format!(
+            "{self}\n{}",
+            underline(
+                self.line,
+                &self.text,
+                self.column(),
+                self.found().chars().count()
+            )
+        )
+    }
+
+    // 1 based, in characters
+    pub fn column(&self) -> usize {
+        self.text[..self.span.start].chars().count() + 1
+    }
+}
+
+impl Display for GameParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: expected {}, ", self.line, self.expected)?;
+        match self.found() {
+            "" => write!(f, "found the end of the line"),
+            found => write!(f, "found {found:?}"),
+        }
+    }
+}
+
+impl std::error::Error for GameParseError {}
+
+impl From<GameParseError> for AocError {
+    fn from(err: GameParseError) -> AocError {
+        let message = match err.found() {
+            "" => format!("expected {}", err.expected),
+            found => format!("expected {}, found {found:?}", err.expected),
+        };
+        let width = err.found().chars().count();
+        AocError::parse_span(err.line, err.column(), width, message)
+    }
+}
@@ -0,0 +1,144 @@
+use serde::Serialize;
+
+use super::{Bag, Color, GameLog};
+
+// The cubes each game needs and how big the draws of each color are
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub games: Vec<GameReport>,
+    // the smallest bag that makes every game possible
+    pub min_bag: Bag,
+    pub colors: Vec<ColorStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameReport {
+    pub id: u32,
+    pub min_bag: Bag,
+    // the game's power as part 2 takes it, None if it overflows
+    pub power: Option<u64>,
+}
+
+// Sizes of the draws of one color, over every round that shows it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColorStats {
+    pub color: Color,
+    pub draws: usize,
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+    // the first game with the largest draw, which sets the color in the overall minimum bag
+    pub max_game: u32,
+}
+
+impl Report {
+    pub fn new(log: &GameLog) -> Report {
+        // the colors part 2 takes powers over, so each row's power is its bag's
+        let colors = log.power_colors();
+
+        let games: Vec<GameReport> = log
+            .games()
+            .iter()
+            .map(|game| {
+                let min_bag = game.min_bag(&colors);
+                GameReport {
+                    id: game.id,
+                    power: min_bag.power(),
+                    min_bag,
+                }
+            })
+            .collect();
+
+        let mut min_bag = Bag::default();
+        let mut stats = vec![];
+        for color in colors {
+            let mut color_stats = ColorStats {
+                color: color.clone(),
+                draws: 0,
+                min: u32::MAX,
+                max: 0,
+                mean: 0.0,
+                max_game: 0,
+            };
+            let mut total = 0u64;
+
+            for game in log.games() {
+                for count in game.rounds.iter().flat_map(|round| round.counts.iter()) {
+                    if count.color != color {
+                        continue;
+                    }
+                    color_stats.draws += 1;
+                    color_stats.min = color_stats.min.min(count.amount);
+                    if count.amount > color_stats.max || color_stats.draws == 1 {
+                        color_stats.max = count.amount;
+                        color_stats.max_game = game.id;
+                    }
+                    total += u64::from(count.amount);
+                }
+            }
+
+            min_bag.set(color, color_stats.max);
+            // red, green or blue when no game draws them, there are no draws to measure
+            if color_stats.draws > 0 {
+                color_stats.mean = total as f64 / color_stats.draws as f64;
+                stats.push(color_stats);
+            }
+        }
+
+        Report {
+            games,
+            min_bag,
+            colors: stats,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day2::one_game, solution::Solution};
+
+    #[test]
+    fn report() {
+        let log = GameLog::parse(include_str!("examples/example.txt")).unwrap();
+        let report = Report::new(&log);
+
+        assert_eq!(
+            report
+                .games
+                .iter()
+                .map(|game| game.power)
+                .collect::<Vec<_>>(),
+            [48, 12, 1560, 630, 36].map(Some)
+        );
+        assert_eq!(
+            report.min_bag,
+            Bag::new([("blue", 15), ("red", 20), ("green", 13)])
+        );
+
+        let red = &report.colors[1];
+        assert_eq!(red.color, Color::red());
+        assert_eq!((red.draws, red.min, red.max, red.max_game), (11, 1, 20, 3));
+        assert!((red.mean - 61.0 / 11.0).abs() < 1e-9);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["min_bag"]["red"], 20);
+        assert_eq!(json["colors"][0]["color"], "blue");
+        assert_eq!(json["games"][2]["min_bag"]["green"], 13);
+
+        // each row's power is the product of its own bag, which always has red, green and blue
+        let report = Report::new(&one_game(&[(3, "blue"), (2, "yellow")]));
+        let game = &report.games[0];
+        assert_eq!(
+            game.min_bag,
+            Bag::new([("blue", 3), ("yellow", 2), ("red", 0), ("green", 0)])
+        );
+        assert_eq!(game.power, Some(0));
+        let drawn: Vec<_> = report
+            .colors
+            .iter()
+            .map(|stats| stats.color.name())
+            .collect();
+        assert_eq!(drawn, vec!["blue", "yellow"]);
+    }
+}
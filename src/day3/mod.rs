@@ -1,8 +1,14 @@
-use crate::utils::read_full_file;
+use log::{debug, trace};
+
+use crate::{
+    error::{AocError, Result},
+    solution::{Answer, Solution},
+    utils,
+};
 use std::{collections::HashSet, fmt::Display, sync::atomic::AtomicUsize};
 
 #[derive(Debug, PartialEq, Clone, Copy, Eq, PartialOrd, Ord)]
-enum GridElement {
+pub enum GridElement {
     Empty,
     Num(u32),
     Part(char),
@@ -19,7 +25,7 @@ impl Display for GridElement {
 }
 
 #[derive(Clone)]
-struct Engine {
+pub struct Engine {
     grid: Vec<Vec<GridElement>>,
 }
 
@@ -58,12 +64,12 @@ impl PartNums {
     }
 }
 
-struct Position {
+pub struct Position {
     x: usize,
     y: usize,
 }
 
-struct GridElementPos {
+pub struct GridElementPos {
     ele: GridElement,
     pos: Position,
 }
@@ -95,8 +101,8 @@ impl Engine {
                 u + i as usize
             }
         }
-        for i in -1 as i32..=1 {
-            for j in -1 as i32..=1 {
+        for i in -1_i32..=1 {
+            for j in -1_i32..=1 {
                 let x_n = add(x, i);
                 let y_n = add(y, j);
                 if let Some(ele) = self.get_grid_element(x_n, y_n) {
@@ -107,27 +113,7 @@ impl Engine {
         eles
     }
 
-    fn parse(str: String) -> Engine {
-        let lines = str.split('\n');
-
-        let mut eng = Engine::new(1000, 1000);
-
-        for (y, line) in lines.into_iter().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                match c {
-                    '.' => eng.set_grid_element(x, y, GridElement::Empty),
-                    _ => match c.to_digit(10) {
-                        Some(digit) => eng.set_grid_element(x, y, GridElement::Num(digit)),
-                        None => eng.set_grid_element(x, y, GridElement::Part(c)),
-                    },
-                }
-            }
-        }
-
-        eng
-    }
-
-    fn find_part_nums(self) -> PartNums {
+    fn find_part_nums(&self) -> PartNums {
         let mut part_nums = PartNums::new(1000, 1000);
 
         self.grid
@@ -148,19 +134,17 @@ impl Engine {
                                 reading_num = true;
                             }
                             current_num = current_num * 10 + n;
-                            let part_nearby = self.get_grid_elements_around(x, y).into_iter().any(
-                                |ele| match ele {
-                                    GridElement::Part(_) => true,
-                                    _ => false,
-                                },
-                            );
+                            let part_nearby = self
+                                .get_grid_elements_around(x, y)
+                                .into_iter()
+                                .any(|ele| matches!(ele, GridElement::Part(_)));
                             if part_nearby {
                                 found_part = part_nearby
                             }
                         }
                         _ => {
                             if found_part {
-                                println!("Found part num: {current_num}");
+                                trace!("Found part num: {current_num}");
                                 part_nums.add_part_num(start_x, x, y, current_num);
                             }
                             start_x = 0;
@@ -197,81 +181,113 @@ impl IntoIterator for Engine {
     }
 }
 
-fn part1(eng: Engine) {
-    let answer: u32 = eng.find_part_nums().nums.into_iter().sum();
+impl Solution for Engine {
+    fn parse(input: &str) -> Result<Engine> {
+        let mut eng = Engine::new(1000, 1000);
 
-    println!("Part1 answer: {answer}");
-}
+        for (line_num, line) in utils::lines(input) {
+            let y = line_num - 1;
+            for (x, c) in line.chars().enumerate() {
+                if y >= eng.grid.len() || x >= eng.grid[y].len() {
+                    return Err(AocError::parse(
+                        y + 1,
+                        x + 1,
+                        "schematic is larger than the 1000x1000 engine grid",
+                    ));
+                }
+
+                match c {
+                    '.' => eng.set_grid_element(x, y, GridElement::Empty),
+                    _ => match c.to_digit(10) {
+                        Some(digit) => eng.set_grid_element(x, y, GridElement::Num(digit)),
+                        None => eng.set_grid_element(x, y, GridElement::Part(c)),
+                    },
+                }
+            }
+        }
 
-fn part2(eng: Engine) {
-    let parts = eng.clone().find_part_nums();
+        Ok(eng)
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        let answer: u32 = self.find_part_nums().nums.into_iter().sum();
 
-    let mut total_gear_ratio = 0;
+        Ok(answer.into())
+    }
 
-    eng.into_iter().for_each(|ele_pos| {
-        if ele_pos.ele == GridElement::Part('*') {
-            println!(
-                "\nFound potential gear {:?} {:?}",
-                ele_pos.pos.x, ele_pos.pos.y
-            );
-            let mut adjacent_part_nums: HashSet<PartNum> = HashSet::new();
+    fn part2(&self) -> Result<Answer> {
+        let parts = self.find_part_nums();
 
-            fn add(u: usize, i: i32) -> usize {
-                if i.is_negative() {
-                    if u == 0 {
-                        return 0;
+        let mut total_gear_ratio = 0;
+
+        self.clone().into_iter().for_each(|ele_pos| {
+            if ele_pos.ele == GridElement::Part('*') {
+                debug!(
+                    "Found potential gear {:?} {:?}",
+                    ele_pos.pos.x, ele_pos.pos.y
+                );
+                let mut adjacent_part_nums: HashSet<PartNum> = HashSet::new();
+
+                fn add(u: usize, i: i32) -> usize {
+                    if i.is_negative() {
+                        if u == 0 {
+                            return 0;
+                        }
+                        u - i.wrapping_abs() as usize
+                    } else {
+                        u + i as usize
                     }
-                    u - i.wrapping_abs() as usize
-                } else {
-                    u + i as usize
                 }
-            }
-            for i in -1 as i32..=1 {
-                for j in -1 as i32..=1 {
-                    let x_n = add(ele_pos.pos.x, i);
-                    let y_n = add(ele_pos.pos.y, j);
-                    if let Some(part) = parts.get_part_num(x_n, y_n) {
-                        adjacent_part_nums.insert(part.clone());
-                        println!(
-                            "Found adjacent part num ({:?}) at pos {x_n} {y_n}: {:?}",
-                            part.num,
-                            adjacent_part_nums.len()
-                        );
-                        // print the elements in the set
-                        for p in adjacent_part_nums.iter() {
-                            println!("Part num: {:?}", p);
+                for i in -1_i32..=1 {
+                    for j in -1_i32..=1 {
+                        let x_n = add(ele_pos.pos.x, i);
+                        let y_n = add(ele_pos.pos.y, j);
+                        if let Some(part) = parts.get_part_num(x_n, y_n) {
+                            adjacent_part_nums.insert(part.clone());
+                            trace!(
+                                "Found adjacent part num ({:?}) at pos {x_n} {y_n}: {:?}",
+                                part.num,
+                                adjacent_part_nums
+                            );
                         }
                     }
                 }
-            }
 
-            let mut part_itter = adjacent_part_nums.into_iter();
+                let mut part_itter = adjacent_part_nums.into_iter();
 
-            let part_num1 = part_itter.next();
-            let part_num2 = part_itter.next();
+                let part_num1 = part_itter.next();
+                let part_num2 = part_itter.next();
 
-            if let Some(num1) = part_num1 {
-                if let Some(num2) = part_num2 {
-                    if part_itter.count() == 0 {
-                        let gear_ratio = num1.num * num2.num;
-                        println!("Found gear {gear_ratio}");
-                        total_gear_ratio += gear_ratio;
+                if let Some(num1) = part_num1 {
+                    if let Some(num2) = part_num2 {
+                        if part_itter.count() == 0 {
+                            let gear_ratio = num1.num * num2.num;
+                            debug!("Found gear {gear_ratio}");
+                            total_gear_ratio += gear_ratio;
+                        }
                     }
                 }
             }
-        }
-    });
+        });
 
-    println!("Part 2 answer: {total_gear_ratio}");
+        Ok(total_gear_ratio.into())
+    }
 }
 
-pub fn main() {
-    let puzzle_input = read_full_file("src/day3/input.txt");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_example;
+
+    #[test]
+    fn part1_example() {
+        let answer = solve_example::<Engine>(include_str!("examples/example.txt"), 1);
+        assert_eq!(answer, Answer::Int(4361));
+    }
 
-    if let Ok(puzzle_input) = puzzle_input {
-        let engine = Engine::parse(puzzle_input);
-        println!("Input parsed");
-        // part1(engine);
-        part2(engine);
+    #[test]
+    fn part2_example() {
+        let answer = solve_example::<Engine>(include_str!("examples/example.txt"), 2);
+        assert_eq!(answer, Answer::Int(467835));
     }
 }
@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 
-use crate::utils::read_lines;
+use crate::{
+    error::{AocError, Result},
+    solution::{Answer, Solution},
+    utils,
+};
 
 pub struct Card {
     pub winning_nums: Vec<u32>,
@@ -8,24 +12,35 @@ pub struct Card {
 }
 
 impl Card {
-    pub fn parse_line(line: String) -> Option<Card> {
-        let mut parts = line.split(":").nth(1)?.split("|");
-
-        let winning_nums: Vec<u32> = parts
-            .next()?
-            .split(" ")
-            .filter_map(|s| s.parse().ok())
-            .collect();
-
-        let other_nums: Vec<u32> = parts
-            .next()?
-            .split(" ")
-            .filter_map(|s| s.parse().ok())
-            .collect();
-
-        Some(Card {
-            winning_nums,
-            nums: other_nums,
+    // line_num is only used for error messages
+    pub fn parse_line(line_num: usize, line: &str) -> Result<Card> {
+        let missing = |what: &str| AocError::parse(line_num, line.chars().count() + 1, what);
+
+        let (_, nums) = line
+            .split_once(':')
+            .ok_or_else(|| missing("expected ':'"))?;
+        let (winning, other) = nums
+            .split_once('|')
+            .ok_or_else(|| missing("expected '|'"))?;
+
+        let parse_nums = |s: &str| -> Result<Vec<u32>> {
+            s.split_whitespace()
+                .map(|num| {
+                    num.parse().map_err(|err| {
+                        AocError::parse_at(
+                            line_num,
+                            line,
+                            num,
+                            format!("invalid number {num}: {err}"),
+                        )
+                    })
+                })
+                .collect()
+        };
+
+        Ok(Card {
+            winning_nums: parse_nums(winning)?,
+            nums: parse_nums(other)?,
         })
     }
 
@@ -43,50 +58,72 @@ impl Card {
             return 0;
         }
 
-        let points = i32::pow(2, (winning_num_count - 1) as u32);
-
-        points
+        i32::pow(2, (winning_num_count - 1) as u32)
     }
 }
 
-pub fn part1(cards: Vec<Card>) {
-    let total_points: i32 = cards.iter().map(|card| card.get_point_value()).sum();
-    println!("Part 1: {}", total_points);
+pub struct Scratchcards {
+    cards: Vec<Card>,
 }
 
-pub fn part2(cards: Vec<Card>) {
-    // maps card number to its count
-    let mut card_count: HashMap<usize, usize> = HashMap::new();
+impl Solution for Scratchcards {
+    fn parse(input: &str) -> Result<Scratchcards> {
+        let cards = utils::lines(input)
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line_num, line)| Card::parse_line(line_num, line))
+            .collect::<Result<Vec<Card>>>()?;
 
-    let mut total_card_count = 0;
+        Ok(Scratchcards { cards })
+    }
 
-    for (i, card) in cards.into_iter().enumerate() {
-        let winning_num_count = card.get_winning_matches();
-        let checking_card_count = match card_count.get(&i) {
-            None => 1,
-            Some(val) => *val,
-        };
+    fn part1(&self) -> Result<Answer> {
+        let total_points: i32 = self.cards.iter().map(|card| card.get_point_value()).sum();
+        Ok(total_points.into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        // maps card number to its count
+        let mut card_count: HashMap<usize, usize> = HashMap::new();
 
-        total_card_count += checking_card_count;
+        let mut total_card_count = 0;
 
-        for j in (i + 1)..=i + winning_num_count {
-            let new_card_count = match card_count.get(&j) {
-                None => checking_card_count + 1,
-                Some(val) => val + checking_card_count,
+        for (i, card) in self.cards.iter().enumerate() {
+            let winning_num_count = card.get_winning_matches();
+            let checking_card_count = match card_count.get(&i) {
+                None => 1,
+                Some(val) => *val,
             };
 
-            card_count.insert(j, new_card_count);
+            total_card_count += checking_card_count;
+
+            for j in (i + 1)..=i + winning_num_count {
+                let new_card_count = match card_count.get(&j) {
+                    None => checking_card_count + 1,
+                    Some(val) => val + checking_card_count,
+                };
+
+                card_count.insert(j, new_card_count);
+            }
         }
-    }
 
-    println!("Part 2: {}", total_card_count);
+        total_card_count.try_into()
+    }
 }
 
-pub fn main() {
-    if let Ok(lines) = read_lines("src/day4/input.txt") {
-        let cards = lines.map(|line| line.unwrap()).filter_map(Card::parse_line);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_example;
+
+    #[test]
+    fn part1_example() {
+        let answer = solve_example::<Scratchcards>(include_str!("examples/example.txt"), 1);
+        assert_eq!(answer, Answer::Int(13));
+    }
 
-        // part1(cards.collect());
-        part2(cards.collect());
+    #[test]
+    fn part2_example() {
+        let answer = solve_example::<Scratchcards>(include_str!("examples/example.txt"), 2);
+        assert_eq!(answer, Answer::Int(30));
     }
 }
@@ -1,6 +1,10 @@
 use regex::Regex;
 
-use crate::utils::read_full_file;
+use crate::{
+    error::{AocError, Result},
+    solution::{Answer, Solution},
+    utils,
+};
 
 struct NumMap {
     source: u64,
@@ -45,72 +49,11 @@ pub struct Garden {
 }
 
 impl Garden {
-    pub fn parse(str: String) -> Option<Garden> {
-        let mut lines = str.split('\n');
-
-        let seed_line = lines.next()?;
-
-        // parse seed line
-        // seeds: 10 20 28 47
-        let seeds: Vec<u64> = seed_line
-            .split(' ')
-            .filter_map(|s| s.parse().ok())
-            .collect();
-
-        let mut maps: Vec<GardenMap> = vec![];
-
-        while lines.clone().count() > 0 {
-            let mut line = lines.next()?;
-
-            if line == "" {
-                continue;
-            }
-
-            // each line looks like "seed-to-soil map:" or "soil-to-water map:"
-            // need to parse out the words "seed" and "soil"
-            let re = Regex::new(r"(\w+)-to-(\w+) map:").ok()?;
-
-            let from = re.captures(&line)?.get(1)?.as_str().to_string();
-            let to = re.captures(&line)?.get(2)?.as_str().to_string();
-
-            line = lines.next()?;
-
-            let mut num_maps: Vec<NumMap> = vec![];
-
-            while line.len() > 0 {
-                let re = Regex::new(r"(\d+) (\d+) (\d+)").ok()?;
-                let cs = re.captures(&line)?;
-                let mut caps = cs.iter().filter_map(|x| x?.as_str().parse::<u64>().ok());
-
-                let dest = caps.next()?;
-                let source = caps.next()?;
-                let range = caps.next()?;
-                let map = NumMap {
-                    dest,
-                    source,
-                    range,
-                };
-                num_maps.push(map);
-                line = lines.next()?;
-            }
-
-            let garden_map = GardenMap {
-                maps: num_maps,
-                to_type: to,
-                from_type: from,
-            };
-
-            maps.push(garden_map);
-        }
-
-        Some(Garden { seeds, maps })
-    }
-
     fn get_map_with_from_type(&self, from_type: String) -> Option<&GardenMap> {
         self.maps.iter().find(|map| map.from_type == from_type)
     }
 
-    pub fn find_smallest_location_numer(&self) -> u64 {
+    pub fn find_smallest_location_numer(&self) -> Option<u64> {
         self.seeds
             .clone()
             .into_iter()
@@ -131,7 +74,6 @@ impl Garden {
                 }
             })
             .min()
-            .unwrap_or(0)
     }
 
     pub fn build_location_to_seed_range_map(&self) {
@@ -140,38 +82,168 @@ impl Garden {
     }
 }
 
+#[allow(dead_code)]
 struct RangeMap {
     to: u32,
     from: u32,
 }
 
+#[allow(dead_code)]
 struct RangeMapHolder {
     range: Vec<RangeMap>,
 }
 
-pub fn part1(garden: Garden) {
-    println!("Part 1: {:?}", garden.find_smallest_location_numer());
-}
+impl Solution for Garden {
+    fn parse(input: &str) -> Result<Garden> {
+        let mut sections = utils::sections(input);
 
-pub fn test() {
-    let mut sum: u64 = 0;
+        // parse seed line
+        // seeds: 10 20 28 47
+        let seed_section = sections
+            .next()
+            .ok_or_else(|| AocError::parse(1, 1, "expected a seeds line"))?;
+        let seed_line = seed_section.header;
+        let seed_nums = seed_line.strip_prefix("seeds:").ok_or_else(|| {
+            AocError::parse(
+                seed_section.line,
+                1,
+                "expected a line like \"seeds: 79 14\"",
+            )
+        })?;
+        let seeds = seed_nums
+            .split_whitespace()
+            .map(|num| {
+                num.parse().map_err(|err| {
+                    AocError::parse_at(
+                        seed_section.line,
+                        seed_line,
+                        num,
+                        format!("invalid seed {num}: {err}"),
+                    )
+                })
+            })
+            .collect::<Result<Vec<u64>>>()?;
+        // the seeds are all on the one line, the maps come after a blank line
+        if !seed_section.body.is_empty() {
+            return Err(AocError::parse(
+                seed_section.line + 1,
+                1,
+                "expected a blank line after the seeds line",
+            ));
+        }
+
+        let mut maps: Vec<GardenMap> = vec![];
+
+        // each header line looks like "seed-to-soil map:" or "soil-to-water map:"
+        // need to parse out the words "seed" and "soil"
+        let header_re = Regex::new(r"^(\w+)-to-(\w+) map:$").unwrap();
+        let map_re = Regex::new(r"^(\d+) (\d+) (\d+)$").unwrap();
+
+        for section in sections {
+            let header = header_re.captures(section.header).ok_or_else(|| {
+                AocError::parse(
+                    section.line,
+                    1,
+                    "expected a header like \"seed-to-soil map:\"",
+                )
+            })?;
+
+            let mut num_maps: Vec<NumMap> = vec![];
+
+            for (line_num, line) in section.numbered_body() {
+                let invalid_map =
+                    || AocError::parse(line_num, 1, "expected a map like \"50 98 2\"");
+                let cs = map_re.captures(line).ok_or_else(invalid_map)?;
+                let mut nums = [0u64; 3];
+                for (num, cap) in nums.iter_mut().zip(cs.iter().skip(1).flatten()) {
+                    *num = cap.as_str().parse().map_err(|err| {
+                        AocError::parse_at(
+                            line_num,
+                            line,
+                            cap.as_str(),
+                            format!("invalid number {}: {err}", cap.as_str()),
+                        )
+                    })?;
+                }
+                let [dest, source, range] = nums;
+                let map = NumMap {
+                    dest,
+                    source,
+                    range,
+                };
+                num_maps.push(map);
+            }
+
+            let garden_map = GardenMap {
+                maps: num_maps,
+                to_type: header[2].to_string(),
+                from_type: header[1].to_string(),
+            };
+
+            maps.push(garden_map);
+        }
 
-    for i in 0..1000000000 {
-        sum += i
+        Ok(Garden { seeds, maps })
     }
 
-    println!("sum {}", sum);
+    fn part1(&self) -> Result<Answer> {
+        self.find_smallest_location_numer()
+            .ok_or_else(|| AocError::no_answer("there are no seeds"))?
+            .try_into()
+    }
 }
 
-pub fn main() {
-    if let Some(file) = read_full_file("./src/day5/input.txt").ok() {
-        let garden = Garden::parse(file);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_example;
+
+    #[test]
+    fn part1_example() {
+        let answer = solve_example::<Garden>(include_str!("examples/example.txt"), 1);
+        assert_eq!(answer, Answer::Int(35));
+    }
+
+    #[test]
+    fn parse_without_trailing_blank_line() {
+        let example = include_str!("examples/example.txt");
+        for input in [
+            example.trim_end().to_string(),
+            example.replace('\n', "\r\n"),
+        ] {
+            let answer = solve_example::<Garden>(&input, 1);
+            assert_eq!(answer, Answer::Int(35));
+        }
+    }
 
-        println!("The garden is parsed");
-        test();
+    #[test]
+    fn parse_error_points_at_the_bad_line() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n50 x 2\n";
+        let err = Garden::parse(input).err().unwrap();
+        assert!(matches!(err, AocError::Parse { line: 5, .. }), "{err}");
+    }
 
-        // if let Some(garden) = garden {
-        //     part1(garden)
-        // }
+    #[test]
+    fn parse_rejects_bad_tokens() {
+        let cases = [
+            ("seeds: 79 x 14\n", 1, 11),
+            ("sedes: 79 14\n", 1, 1),
+            ("seeds: 79 14\n55 13\n\nseed-to-soil map:\n50 98 2\n", 2, 1),
+            ("seeds:\n79 14\n", 2, 1),
+            ("seeds: 79\n\nseed-to-soil map:\n50 98 2 7\n", 4, 1),
+            ("seeds: 79\n\nseed-to-soil map: extra\n50 98 2\n", 3, 1),
+            (
+                "seeds: 79\n\nseed-to-soil map:\n50 99999999999999999999 2\n",
+                4,
+                4,
+            ),
+        ];
+        for (input, line, column) in cases {
+            let err = Garden::parse(input).err().unwrap();
+            assert!(
+                matches!(err, AocError::Parse { line: l, column: c, .. } if (l, c) == (line, column)),
+                "{input:?}: {err}"
+            );
+        }
     }
 }
@@ -0,0 +1,131 @@
+use std::{fmt::Display, io};
+
+#[derive(Debug)]
+pub enum AocError {
+    Io {
+        context: String,
+        source: io::Error,
+    },
+    // line and column are 1 based, width is how many characters the offending text spans
+    Parse {
+        line: usize,
+        column: usize,
+        width: usize,
+        message: String,
+    },
+    NoAnswer(String),
+    // The default for parts that have not been written yet
+    Unsolved,
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    pub fn io(context: impl Into<String>, source: io::Error) -> AocError {
+        AocError::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> AocError {
+        AocError::parse_span(line, column, 1, message)
+    }
+
+    pub fn parse_span(
+        line: usize,
+        column: usize,
+        width: usize,
+        message: impl Into<String>,
+    ) -> AocError {
+        AocError::Parse {
+            line,
+            column,
+            width: width.max(1),
+            message: message.into(),
+        }
+    }
+
+    // A parse error pointing at `token`, which must be a slice of `line_text`
+    pub fn parse_at(
+        line: usize,
+        line_text: &str,
+        token: &str,
+        message: impl Into<String>,
+    ) -> AocError {
+        let offset = (token.as_ptr() as usize).saturating_sub(line_text.as_ptr() as usize);
+        let column = line_text[..offset.min(line_text.len())].chars().count() + 1;
+        AocError::parse_span(line, column, token.chars().count(), message)
+    }
+
+    // A parse error at a byte offset into a whole file
+    pub fn parse_at_offset(input: &str, offset: usize, message: impl Into<String>) -> AocError {
+        let before = &input[..offset.min(input.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        AocError::parse(line, column, message)
+    }
+
+    pub fn no_answer(reason: impl Into<String>) -> AocError {
+        AocError::NoAnswer(reason.into())
+    }
+
+    // The error, followed by the offending line of `input` with the offending text
+    // underlined for parse errors, e.g.
+    //
+    // line 2, column 19: expected a number of cubes, found "x"
+    //     2 | Game 2: 3 purple, x red
+    //       |                   ^
+    pub fn render(&self, input: &str) -> String {
+        let AocError::Parse {
+            line,
+            column,
+            width,
+            ..
+        } = self
+        else {
+            return self.to_string();
+        };
+        match input.lines().nth(line.saturating_sub(1)) {
+            Some(text) => format!("{self}\n{}", underline(*line, text, *column, *width)),
+            None => self.to_string(),
+        }
+    }
+}
+
+// Line `line` of the input, `text`, with `width` characters from `column` underlined. Both
+// are 1 based and count characters
+pub fn underline(line: usize, text: &str, column: usize, width: usize) -> String {
+    let gutter = line.to_string().len().max(4);
+    format!(
+        "{line:>gutter$} | {text}\n{:>gutter$} | {}{}",
+        "",
+        " ".repeat(column.saturating_sub(1)),
+        "^".repeat(width.max(1))
+    )
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Io { context, source } => write!(f, "{}: {}", context, source),
+            AocError::Parse {
+                line,
+                column,
+                message,
+                ..
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            AocError::NoAnswer(reason) => write!(f, "no answer: {}", reason),
+            AocError::Unsolved => write!(f, "not solved yet"),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+// Large random inputs shaped like the puzzle inputs, for the benchmarks and tests. They are
+// seeded so every run sees the same input.
+
+use crate::day1::DIGIT_WORDS;
+
+// xorshift, plenty for making up inputs
+pub struct Random(u64);
+
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        // xorshift is stuck at 0
+        Random(seed.max(1))
+    }
+
+    pub fn next_usize(&mut self) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 as usize
+    }
+
+    // A number from 0 up to but not including `n`
+    pub fn below(&mut self, n: usize) -> usize {
+        self.next_usize() % n
+    }
+}
+
+// Lines of random letters with the occasional digit or digit word, like the day 1 input
+pub fn calibration_document(lines: usize) -> String {
+    let mut random = Random::new(0x2023);
+    let mut document = String::new();
+    for _ in 0..lines {
+        for _ in 0..(20 + random.below(40)) {
+            match random.below(16) {
+                0 => document.push_str(DIGIT_WORDS[random.below(DIGIT_WORDS.len())].0),
+                1 => document.push((b'1' + random.below(9) as u8) as char),
+                _ => document.push((b'a' + random.below(26) as u8) as char),
+            }
+        }
+        document.push('\n');
+    }
+    document
+}
+
+// Games of one to six rounds drawing from red, green and blue, like the day 2 input
+pub fn game_log(games: usize) -> String {
+    let colors = ["red", "green", "blue"];
+    let mut random = Random::new(0x2023);
+    let mut log = String::new();
+    for id in 1..=games {
+        log.push_str(&format!("Game {id}:"));
+        for round in 0..(1 + random.below(6)) {
+            if round > 0 {
+                log.push(';');
+            }
+            let first = random.below(3);
+            for i in 0..(1 + random.below(3)) {
+                if i > 0 {
+                    log.push(',');
+                }
+                let color = colors[(first + i) % 3];
+                log.push_str(&format!(" {} {color}", 1 + random.below(20)));
+            }
+        }
+        log.push('\n');
+    }
+    log
+}
@@ -1,10 +1,11 @@
+pub mod answers;
+pub mod bench;
 pub mod day1;
 pub mod day2;
 pub mod day3;
 pub mod day4;
 pub mod day5;
+pub mod error;
+pub mod generate;
+pub mod solution;
 pub mod utils;
-
-fn main() {
-    day5::main();
-}
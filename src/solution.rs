@@ -0,0 +1,209 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    day1, day2, day3, day4, day5,
+    error::{AocError, Result},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Int(n.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_int!(i32, i64, u32);
+
+// Wider answers than i64 holds are an error rather than wrapping around
+macro_rules! impl_answer_try_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<$t> for Answer {
+                type Error = AocError;
+
+                fn try_from(n: $t) -> Result<Answer> {
+                    i64::try_from(n).map(Answer::Int).map_err(|_| {
+                        AocError::no_answer(format!("{n} is too big for an answer"))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_try_from_int!(u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+pub trait Solution {
+    fn parse(input: &str) -> Result<Self>
+    where
+        Self: Sized;
+
+    // Like `parse`, but also fails on input that `parse` skips with a warning
+    fn parse_strict(input: &str) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::parse(input)
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        Err(AocError::Unsolved)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        Err(AocError::Unsolved)
+    }
+
+    fn part(&self, part: u8) -> Result<Answer> {
+        match part {
+            1 => self.part1(),
+            2 => self.part2(),
+            _ => Err(AocError::Unsolved),
+        }
+    }
+}
+
+pub struct Day {
+    pub day: u8,
+    parse: fn(&str, bool) -> Result<Box<dyn Solution>>,
+}
+
+fn parse_boxed<S: Solution + 'static>(input: &str, strict: bool) -> Result<Box<dyn Solution>> {
+    if strict {
+        Ok(Box::new(S::parse_strict(input)?))
+    } else {
+        Ok(Box::new(S::parse(input)?))
+    }
+}
+
+impl Day {
+    pub const fn new<S: Solution + 'static>(day: u8) -> Day {
+        Day {
+            day,
+            parse: parse_boxed::<S>,
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Solution>> {
+        (self.parse)(input, false)
+    }
+
+    pub fn parse_with(&self, input: &str, strict: bool) -> Result<Box<dyn Solution>> {
+        (self.parse)(input, strict)
+    }
+}
+
+// Every solved day, in order. Add a line here once a day implements `Solution`.
+pub static DAYS: &[Day] = &[
+    Day::new::<day1::CalibrationDocument>(1),
+    Day::new::<day2::GameLog>(2),
+    Day::new::<day3::Engine>(3),
+    Day::new::<day4::Scratchcards>(4),
+    Day::new::<day5::Garden>(5),
+];
+
+pub fn get_day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+// Parses and solves one part of an example input, panicking on any error
+#[cfg(test)]
+pub fn solve_example<S: Solution>(input: &str, part: u8) -> Answer {
+    S::parse(input)
+        .expect("example should parse")
+        .part(part)
+        .expect("example should be solved")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::Input;
+
+    fn answers(day: &Day, input: &str) -> Vec<std::result::Result<String, String>> {
+        let solution = day.parse(input);
+        [1, 2]
+            .into_iter()
+            .map(|part| match &solution {
+                Ok(solution) => solution
+                    .part(part)
+                    .map(|answer| answer.to_string())
+                    .map_err(|err| err.to_string()),
+                Err(err) => Err(err.to_string()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn wide_answers_are_an_error() {
+        assert_eq!(
+            Answer::try_from(u64::MAX >> 1).unwrap(),
+            Answer::Int(i64::MAX)
+        );
+        assert!(matches!(
+            Answer::try_from(1u64 << 63),
+            Err(AocError::NoAnswer(_))
+        ));
+        assert!(matches!(
+            Answer::try_from(usize::MAX),
+            Err(AocError::NoAnswer(_))
+        ));
+    }
+
+    #[test]
+    fn crlf_inputs_give_the_same_answers() {
+        let examples = [
+            (1, "examples/part1.txt"),
+            (1, "examples/part2.txt"),
+            (2, "examples/example.txt"),
+            (3, "examples/example.txt"),
+            (4, "examples/example.txt"),
+            (5, "examples/example.txt"),
+        ];
+        let inputs = DAYS.iter().map(|day| (day.day, "input.txt"));
+
+        for (n, name) in examples.into_iter().chain(inputs) {
+            let day = get_day(n).unwrap();
+            let lf = Input::named(n, name).read().unwrap();
+            let crlf = lf.replace('\n', "\r\n");
+            let padded = lf.replace('\n', "  \t\n");
+            assert_eq!(answers(day, &lf), answers(day, &crlf), "day {n} {name}");
+            assert_eq!(answers(day, &lf), answers(day, &padded), "day {n} {name}");
+        }
+    }
+}
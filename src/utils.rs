@@ -1,24 +1,248 @@
 use std::{
-    fs::File,
+    env,
+    fmt::Display,
+    fs::{self, File},
     io::{self, BufRead, Read},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
-pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-pub fn read_full_file<P>(filename: P) -> io::Result<String>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    let mut buf_reader = io::BufReader::new(file);
-    let mut contents = String::new();
-    buf_reader.read_to_string(&mut contents)?;
-    Ok(contents)
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+
+use crate::error::{AocError, Result};
+
+// Reads a file that is not a puzzle input, like answers.toml
+pub fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|err| AocError::io(format!("reading {}", path.display()), err))
+}
+
+// Parses a TOML file, pointing parse errors at the offending line and column
+pub fn parse_toml<T: DeserializeOwned>(str: &str) -> Result<T> {
+    toml::from_str(str).map_err(|err| {
+        let offset = err.span().map(|span| span.start).unwrap_or(0);
+        AocError::parse_at_offset(str, offset, err.message())
+    })
+}
+
+// Reads a file and hands it to `parse`, for the `load` functions of the config files
+pub fn load_with<T>(path: &Path, parse: impl FnOnce(&str) -> Result<T>) -> Result<T> {
+    parse(&read_file(path)?)
+}
+
+// Overrides the directory puzzle inputs are read from, laid out as `$AOC_INPUT_DIR/dayN/input.txt`
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Input {
+    // An explicit path wins, `-` means stdin, otherwise the day's input.txt is used
+    pub fn resolve(day: u8, explicit: Option<&Path>) -> Input {
+        match explicit {
+            Some(path) if path == Path::new("-") => Input::Stdin,
+            Some(path) => Input::File(path.to_path_buf()),
+            None => Input::named(day, "input.txt"),
+        }
+    }
+
+    // A file in the day's input directory, e.g. `test.txt`
+    pub fn named(day: u8, name: &str) -> Input {
+        Input::File(day_input_dir(day).join(name))
+    }
+
+    // The inverse of `named`, None if the input is not in the day's input directory
+    pub fn name(&self, day: u8) -> Option<String> {
+        let Input::File(path) = self else {
+            return None;
+        };
+        let path = fs::canonicalize(path).ok()?;
+        let dir = fs::canonicalize(day_input_dir(day)).ok()?;
+        Some(path.strip_prefix(dir).ok()?.to_str()?.to_string())
+    }
+
+    pub fn read(&self) -> Result<String> {
+        match self {
+            Input::Stdin => {
+                let mut contents = String::new();
+                io::stdin()
+                    .read_to_string(&mut contents)
+                    .map_err(|err| AocError::io("reading stdin", err))?;
+                Ok(contents)
+            }
+            Input::File(path) => fs::read_to_string(path).map_err(|err| file_error(path, err)),
+        }
+    }
+
+    // A reader over the input for streaming it a line at a time instead of reading it whole
+    pub fn open(&self) -> Result<Box<dyn BufRead>> {
+        match self {
+            Input::Stdin => Ok(Box::new(io::stdin().lock())),
+            Input::File(path) => {
+                let file = File::open(path).map_err(|err| file_error(path, err))?;
+                Ok(Box::new(io::BufReader::new(file)))
+            }
+        }
+    }
+}
+
+fn file_error(path: &Path, err: io::Error) -> AocError {
+    match err.kind() {
+        io::ErrorKind::NotFound => AocError::io(
+            format!(
+                "input file {} does not exist (pass --input or set {INPUT_DIR_VAR})",
+                path.display()
+            ),
+            err,
+        ),
+        _ => AocError::io(format!("reading {}", path.display()), err),
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Input::Stdin => write!(f, "<stdin>"),
+            Input::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+fn day_input_dir(day: u8) -> PathBuf {
+    let root = match env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+    };
+    root.join(format!("day{day}"))
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+// Trailing whitespace never matters in a puzzle input, and trimming it also drops the `\r` of
+// `\r\n` line endings, so inputs saved on Windows parse the same
+pub fn normalize_line(line: &str) -> &str {
+    line.trim_end()
+}
+
+// The normalized lines of a puzzle input numbered from 1
+pub fn lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, normalize_line(line)))
+}
+
+// A run of non-blank lines
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block<'a> {
+    // number of the first line, 1 based
+    pub line: usize,
+    pub lines: Vec<&'a str>,
+}
+
+// The blocks of an input separated by one or more blank lines
+pub fn blocks(input: &str) -> impl Iterator<Item = Block<'_>> {
+    let mut lines = lines(input).peekable();
+    std::iter::from_fn(move || {
+        while lines.next_if(|(_, line)| line.trim().is_empty()).is_some() {}
+
+        let (line, first) = lines.next()?;
+        let mut block = Block {
+            line,
+            lines: vec![first],
+        };
+        while let Some((_, line)) = lines.next_if(|(_, line)| !line.trim().is_empty()) {
+            block.lines.push(line);
+        }
+        Some(block)
+    })
+}
+
+// A block whose first line names what the rest of it holds, e.g. "seed-to-soil map:"
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section<'a> {
+    // number of the header line, 1 based
+    pub line: usize,
+    pub header: &'a str,
+    pub body: Vec<&'a str>,
+}
+
+impl<'a> Section<'a> {
+    // The body lines numbered from 1 within the whole input
+    pub fn numbered_body(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.body
+            .iter()
+            .enumerate()
+            .map(|(i, line)| (self.line + i + 1, *line))
+    }
+}
+
+// The blank line separated sections of an input, each split into its header and body
+pub fn sections(input: &str) -> impl Iterator<Item = Section<'_>> {
+    blocks(input).map(|block| Section {
+        line: block.line,
+        header: block.lines[0],
+        body: block.lines[1..].to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_are_split_on_blank_lines() {
+        let input = "\na\nb\n\n\nc\n  \nd\ne";
+        let found: Vec<Block> = blocks(input).collect();
+        assert_eq!(
+            found,
+            vec![
+                Block {
+                    line: 2,
+                    lines: vec!["a", "b"]
+                },
+                Block {
+                    line: 6,
+                    lines: vec!["c"]
+                },
+                Block {
+                    line: 8,
+                    lines: vec!["d", "e"]
+                },
+            ]
+        );
+        assert_eq!(blocks("").count(), 0);
+        assert_eq!(blocks("\n\n").count(), 0);
+    }
+
+    #[test]
+    fn sections_have_a_header_and_body() {
+        let input =
+            "seeds: 1 2\r\n\r\na-to-b map:  \r\n1 2 3\r\n4 5 6 \r\n\r\nb-to-c map:\r\n7 8 9";
+        let found: Vec<Section> = sections(input).collect();
+        assert_eq!(found.len(), 3);
+        assert_eq!((found[0].header, found[0].body.len()), ("seeds: 1 2", 0));
+        assert_eq!(found[1].header, "a-to-b map:");
+        assert_eq!(
+            found[1].numbered_body().collect::<Vec<_>>(),
+            vec![(4, "1 2 3"), (5, "4 5 6")]
+        );
+        assert_eq!(
+            found[2].numbered_body().collect::<Vec<_>>(),
+            vec![(8, "7 8 9")]
+        );
+    }
+
+    #[test]
+    fn lines_are_normalized() {
+        let found: Vec<(usize, &str)> = lines("a \r\nb\t\n\r\nc\r").collect();
+        assert_eq!(found, vec![(1, "a"), (2, "b"), (3, ""), (4, "c")]);
+    }
 }